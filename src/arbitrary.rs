@@ -0,0 +1,218 @@
+//! [proptest](https://crates.io/crates/proptest) strategies for generating
+//! random but valid assets, e.g. for fuzzing accounting logic
+//!
+//! Available with the `proptest` feature. [`AssetInfo`], [`Asset`] and
+//! [`AssetList`] also implement proptest's `Arbitrary` trait, so they can be
+//! generated with `any::<T>()`:
+//!
+//! ```rust
+//! use cw_asset::{Asset, AssetList};
+//! use proptest::prelude::*;
+//!
+//! proptest! {
+//!     // in a test suite, this function would be annotated with `#[test]`
+//!     fn adding_an_asset(mut list in any::<AssetList>(), asset in any::<Asset>()) {
+//!         list.add(&asset).unwrap();
+//!     }
+//! }
+//!
+//! adding_an_asset();
+//! ```
+
+use cosmwasm_std::{testing::MockApi, Addr, Uint128};
+use proptest::{
+    arbitrary::Arbitrary,
+    collection::vec,
+    prelude::*,
+    strategy::{BoxedStrategy, Strategy},
+};
+
+use crate::{Asset, AssetInfo, AssetList};
+
+/// The default upper bound of generated amounts
+///
+/// Amounts are bounded so that the sum of a reasonable number of them cannot
+/// overflow `Uint128`.
+pub const MAX_AMOUNT: u128 = u64::MAX as u128;
+
+/// The default maximum number of assets in a generated **asset list**
+pub const MAX_LIST_LEN: usize = 8;
+
+/// Generate a plain SDK denom, e.g. `uatom`
+pub fn sdk_denom() -> impl Strategy<Value = String> {
+    "[a-z][a-z0-9]{2,15}"
+}
+
+/// Generate an IBC denom, e.g. `ibc/27394FB0...`
+pub fn ibc_denom() -> impl Strategy<Value = String> {
+    "[0-9A-F]{64}".prop_map(|hash| format!("ibc/{hash}"))
+}
+
+/// Generate a token factory denom, e.g. `factory/cosmwasm1.../uastro`, whose
+/// creator is an address generated by [`address`]
+pub fn factory_denom() -> impl Strategy<Value = String> {
+    (address(), sdk_denom()).prop_map(|(creator, subdenom)| format!("factory/{creator}/{subdenom}"))
+}
+
+/// Generate a denom of any of the supported kinds
+pub fn denom() -> impl Strategy<Value = String> {
+    prop_oneof![sdk_denom(), ibc_denom(), factory_denom()]
+}
+
+/// Generate a bech32 address that is valid according to cosmwasm-std's
+/// `MockApi`
+pub fn address() -> impl Strategy<Value = Addr> {
+    "[a-z0-9_]{1,32}".prop_map(|seed| MockApi::default().addr_make(&seed))
+}
+
+/// Generate an amount between zero and `max`, inclusive
+pub fn amount(max: u128) -> impl Strategy<Value = Uint128> {
+    (0..=max).prop_map(Uint128::new)
+}
+
+/// Generate an **asset info** of either a native coin or a CW20 token
+pub fn asset_info() -> impl Strategy<Value = AssetInfo> {
+    prop_oneof![denom().prop_map(AssetInfo::native), address().prop_map(AssetInfo::cw20)]
+}
+
+/// Generate an **asset** whose amount is no greater than `max_amount`
+pub fn asset(max_amount: u128) -> impl Strategy<Value = Asset> {
+    (asset_info(), amount(max_amount)).prop_map(|(info, amount)| Asset::new(info, amount))
+}
+
+/// Generate an **asset list** of up to `max_len` assets, each with an amount
+/// no greater than `max_amount`
+///
+/// Like lists built using [`AssetList::add`], the generated list contains no
+/// duplicate asset infos nor zero amounts.
+pub fn asset_list(max_len: usize, max_amount: u128) -> impl Strategy<Value = AssetList> {
+    vec(asset(max_amount), 0..=max_len).prop_map(|assets| {
+        let mut list = AssetList::new();
+        for asset in &assets {
+            // asset infos are unique with overwhelming probability, so the sum
+            // of amounts is at most `max_len * max_amount`
+            list.add(asset).expect("sum of generated amounts overflowed");
+        }
+        list
+    })
+}
+
+impl Arbitrary for AssetInfo {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        asset_info().boxed()
+    }
+}
+
+impl Arbitrary for Asset {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        asset(MAX_AMOUNT).boxed()
+    }
+}
+
+impl Arbitrary for AssetList {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        asset_list(MAX_LIST_LEN, MAX_AMOUNT).boxed()
+    }
+}
+
+//------------------------------------------------------------------------------
+// Tests
+//------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+    use crate::{AssetInfoUnchecked, AssetListUnchecked, AssetUnchecked};
+
+    fn sorted(list: &AssetList) -> Vec<(String, Uint128)> {
+        let mut assets = list
+            .into_iter()
+            .map(|asset| (asset.info.to_string(), asset.amount))
+            .collect::<Vec<_>>();
+        assets.sort();
+        assets
+    }
+
+    proptest! {
+        #[test]
+        fn asset_info_string_round_trip(info in any::<AssetInfo>()) {
+            let api = MockApi::default();
+            let parsed = AssetInfoUnchecked::from_str(&info.to_string()).unwrap();
+            prop_assert_eq!(parsed.check(&api, None).unwrap(), info);
+        }
+
+        #[test]
+        fn asset_string_round_trip(asset in any::<Asset>()) {
+            let api = MockApi::default();
+            let parsed = AssetUnchecked::from_str(&asset.to_string()).unwrap();
+            prop_assert_eq!(parsed.check(&api, None).unwrap(), asset);
+        }
+
+        #[test]
+        fn asset_list_string_round_trip(list in asset_list(MAX_LIST_LEN, MAX_AMOUNT)) {
+            // an empty list is stringified as `[]`, which is not parsable
+            prop_assume!(!list.is_empty());
+
+            let api = MockApi::default();
+            let parsed = AssetListUnchecked::from_str(&list.to_string()).unwrap();
+            prop_assert_eq!(parsed.check(&api, None).unwrap(), list);
+        }
+
+        #[test]
+        fn adding_then_deducting_is_identity(list in any::<AssetList>(), asset in any::<Asset>()) {
+            let mut new_list = list.clone();
+            new_list.add(&asset).unwrap();
+
+            let before = list.find(&asset.info).map(|a| a.amount).unwrap_or_default();
+            let after = new_list.find(&asset.info).map(|a| a.amount).unwrap_or_default();
+            prop_assert_eq!(after, before + asset.amount);
+
+            if !asset.amount.is_zero() {
+                new_list.deduct(&asset).unwrap();
+            }
+            prop_assert_eq!(new_list, list);
+        }
+
+        #[test]
+        fn adding_many_is_commutative(a in any::<AssetList>(), b in any::<AssetList>()) {
+            let mut a_plus_b = a.clone();
+            a_plus_b.add_many(&b).unwrap();
+
+            let mut b_plus_a = b.clone();
+            b_plus_a.add_many(&a).unwrap();
+
+            prop_assert_eq!(sorted(&a_plus_b), sorted(&b_plus_a));
+
+            a_plus_b.deduct_many(&b).unwrap();
+            prop_assert_eq!(sorted(&a_plus_b), sorted(&a));
+        }
+
+        #[test]
+        fn deducting_too_much_fails(list in any::<AssetList>(), extra in 1..=MAX_AMOUNT) {
+            prop_assume!(!list.is_empty());
+
+            let mut asset = list[0].clone();
+            asset.amount += Uint128::new(extra);
+
+            let mut new_list = list.clone();
+            prop_assert!(new_list.deduct(&asset).is_err());
+            prop_assert_eq!(new_list, list);
+        }
+
+        #[test]
+        fn generated_lists_are_purged(list in any::<AssetList>()) {
+            prop_assert!(list.into_iter().all(|asset| !asset.amount.is_zero()));
+        }
+    }
+}
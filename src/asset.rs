@@ -125,6 +125,18 @@ impl From<Asset> for AssetUnchecked {
     }
 }
 
+/// Split an SDK coin string of the format `{amount}{denom}` into its denom and
+/// amount; see [`AssetUnchecked::from_sdk_string`] for details
+pub(crate) fn parse_sdk_coin(s: &str) -> Result<(&str, Uint128), AssetError> {
+    let invalid = || AssetError::InvalidSdkCoin {
+        coin_str: s.into(),
+    };
+
+    let (i, _) = s.char_indices().find(|(_, c)| !c.is_ascii_digit()).ok_or_else(invalid)?;
+    let amount = Uint128::from_str(&s[..i]).map_err(|_| invalid())?;
+    Ok((&s[i..], amount))
+}
+
 impl AssetUnchecked {
     /// Parse a string of the format `{amount}{denom}` into an `AssetUnchecked`
     /// object. This is the format that Cosmos SDK uses to stringify native
@@ -139,18 +151,12 @@ impl AssetUnchecked {
     /// consider the first non-numerical character and all that comes after as
     /// the denom, while all that comes before it as the amount. This is the
     /// approach used in the [Steak Hub contract](https://github.com/st4k3h0us3/steak-contracts/blob/v1.0.0/contracts/hub/src/helpers.rs#L48-L68).
+    ///
+    /// Returns an `InvalidSdkCoin` error if either the amount or the denom is
+    /// missing, or if the amount does not fit into an 128-bit unsigned integer.
     pub fn from_sdk_string(s: &str) -> Result<Self, AssetError> {
-        for (i, c) in s.chars().enumerate() {
-            if !c.is_ascii_digit() {
-                let amount = Uint128::from_str(&s[..i])?;
-                let denom = &s[i..];
-                return Ok(Self::native(denom, amount));
-            }
-        }
-
-        Err(AssetError::InvalidSdkCoin {
-            coin_str: s.into(),
-        })
+        let (denom, amount) = parse_sdk_coin(s)?;
+        Ok(Self::native(denom, amount))
     }
 
     /// Validate data contained in an _unchecked_ **asset** instnace, return a
@@ -244,6 +250,24 @@ impl std::cmp::PartialEq<Coin> for Asset {
 }
 
 impl Asset {
+    /// Stringify a native coin into the `{amount}{denom}` format used by the
+    /// Cosmos SDK, e.g. `12345uatom`. This is the inverse of
+    /// [`AssetUnchecked::from_sdk_string`].
+    ///
+    /// NOTE: Only works for native coins. Returns error if invoked on an
+    /// `Asset` instance representing a CW20 token, as CW20 tokens do not have
+    /// an SDK coin representation.
+    ///
+    /// ```rust
+    /// use cw_asset::Asset;
+    ///
+    /// let asset = Asset::native("uatom", 12345u128);
+    /// let s = asset.to_sdk_string().unwrap(); // should be `12345uatom`
+    /// ```
+    pub fn to_sdk_string(&self) -> Result<String, AssetError> {
+        Coin::try_from(self).map(|coin| coin.to_string())
+    }
+
     /// Generate a message that sends a CW20 token to the specified recipient
     /// with a binary payload.
     ///
@@ -289,6 +313,54 @@ impl Asset {
         }
     }
 
+    /// Generate a message that deposits the asset into a contract, along with
+    /// a binary payload.
+    ///
+    /// - For CW20 tokens, this is a CW20 `Send` message; the contract receives
+    ///   the payload wrapped in a `Cw20ReceiveMsg`.
+    /// - For native coins, this executes the contract with the payload as the
+    ///   message, and the coin attached as funds.
+    ///
+    /// The contract therefore must accept the payload both as a CW20 receive
+    /// hook and as an execute message. To deposit into a contract dispatching
+    /// the assets it receives using [`ReceiveMsg`](crate::ReceiveMsg), use
+    /// [`Asset::deposit_hook_msg`] instead.
+    ///
+    /// ```rust
+    /// use serde::Serialize;
+    ///
+    /// #[derive(Serialize)]
+    /// enum MockExecuteMsg {
+    ///     Deposit {},
+    /// }
+    ///
+    /// use cosmwasm_std::{to_json_binary, Addr, Response};
+    /// use cw_asset::{Asset, AssetError};
+    ///
+    /// fn forward_asset(asset: &Asset, vault_addr: &Addr) -> Result<Response, AssetError> {
+    ///     let msg = asset.deposit_msg(vault_addr, to_json_binary(&MockExecuteMsg::Deposit {})?)?;
+    ///
+    ///     Ok(Response::new().add_message(msg).add_attribute("asset_deposited", asset.to_string()))
+    /// }
+    /// ```
+    pub fn deposit_msg<A: Into<String>>(
+        &self,
+        contract: A,
+        msg: Binary,
+    ) -> Result<CosmosMsg, AssetError> {
+        match &self.info {
+            AssetInfo::Cw20(_) => self.send_msg(contract, msg),
+            AssetInfo::Native(denom) => Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: contract.into(),
+                msg,
+                funds: vec![Coin {
+                    denom: denom.clone(),
+                    amount: self.amount,
+                }],
+            })),
+        }
+    }
+
     /// Generate a message that transfers the asset from the sender to to a
     /// specified account.
     ///
@@ -546,6 +618,43 @@ mod tests {
 
         let err = AssetUnchecked::from_sdk_string("ngmi");
         assert!(err.is_err());
+
+        let s = "12345";
+        assert_eq!(
+            AssetUnchecked::from_sdk_string(s),
+            Err(AssetError::InvalidSdkCoin {
+                coin_str: s.into(),
+            }),
+        );
+
+        let s = "340282366920938463463374607431768211456uatom";
+        assert_eq!(
+            AssetUnchecked::from_sdk_string(s),
+            Err(AssetError::InvalidSdkCoin {
+                coin_str: s.into(),
+            }),
+        );
+    }
+
+    #[test]
+    fn to_sdk_string() {
+        let asset = Asset::native("uatom", 12345u128);
+        assert_eq!(asset.to_sdk_string().unwrap(), String::from("12345uatom"));
+
+        let asset = Asset::native(
+            "ibc/27394FB092D2ECCD56123C74F36E4C1F926001CEADA9CA97EA622B25F41E5EB2",
+            69420u128,
+        );
+        let s = asset.to_sdk_string().unwrap();
+        assert_eq!(AssetUnchecked::from_sdk_string(&s).unwrap(), asset.into());
+
+        let asset = Asset::cw20(Addr::unchecked("mock_token"), 88888u128);
+        assert_eq!(
+            asset.to_sdk_string(),
+            Err(AssetError::CannotCastToStdCoin {
+                asset: "cw20:mock_token:88888".into(),
+            }),
+        );
     }
 
     #[test]
@@ -614,7 +723,7 @@ mod tests {
             })
         );
 
-        let err = coin.send_msg("mock_contract", bin_msg);
+        let err = coin.send_msg("mock_contract", bin_msg.clone());
         assert_eq!(
             err,
             Err(AssetError::UnavailableMethodForNative {
@@ -622,6 +731,19 @@ mod tests {
             }),
         );
 
+        let msg = token.deposit_msg("mock_contract", bin_msg.clone()).unwrap();
+        assert_eq!(msg, token.send_msg("mock_contract", bin_msg.clone()).unwrap());
+
+        let msg = coin.deposit_msg("mock_contract", bin_msg.clone()).unwrap();
+        assert_eq!(
+            msg,
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: String::from("mock_contract"),
+                msg: bin_msg,
+                funds: vec![Coin::new(123456u128, "uusd")]
+            }),
+        );
+
         let msg = token.transfer_msg("alice").unwrap();
         assert_eq!(
             msg,
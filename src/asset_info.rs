@@ -212,51 +212,55 @@ impl AssetInfo {
             },
         }
     }
+}
 
-    /// Implemented as private function to prevent from_str from being called on AssetInfo
-    fn from_str(s: &str) -> Result<Self, AssetError> {
-        let words: Vec<&str> = s.split(':').collect();
+// Storage key implementations
+//
+// An **asset info** is serialized into two key elements: the asset type (either
+// `native:` or `cw20:`), followed by the denom or contract address. Since the
+// type is written as a separate, length-prefixed element, a map keyed by asset
+// info can be prefixed by the type to iterate over all assets of that type.
+//
+// `PrimaryKey` and `Prefixer` are implemented for the owned types; the
+// reference types (e.g. `&AssetInfo`) are covered by the blanket implementations
+// in `cw_storage_plus`.
+
+impl<'a> PrimaryKey<'a> for AssetInfo {
+    type Prefix = String;
+    type SubPrefix = ();
+    type Suffix = String;
+    type SuperSuffix = Self;
 
-        match words[0] {
-            "native" => {
-                if words.len() != 2 {
-                    return Err(AssetError::InvalidAssetInfoFormat {
-                        received: s.into(),
-                        should_be: "native:{denom}".into(),
-                    });
-                }
-                Ok(AssetInfo::Native(String::from(words[1])))
+    fn key(&self) -> Vec<Key<'_>> {
+        let mut keys = vec![];
+        match &self {
+            AssetInfo::Cw20(addr) => {
+                keys.extend("cw20:".key());
+                keys.extend(addr.key());
             },
-            "cw20" => {
-                if words.len() != 2 {
-                    return Err(AssetError::InvalidAssetInfoFormat {
-                        received: s.into(),
-                        should_be: "cw20:{contract_addr}".into(),
-                    });
-                }
-                Ok(AssetInfo::Cw20(Addr::unchecked(words[1])))
+            AssetInfo::Native(denom) => {
+                keys.extend("native:".key());
+                keys.extend(denom.key());
             },
-            ty => Err(AssetError::InvalidAssetType {
-                ty: ty.into(),
-            }),
-        }
+        };
+        keys
     }
 }
 
-impl<'a> PrimaryKey<'a> for &AssetInfo {
+impl<'a> PrimaryKey<'a> for AssetInfoUnchecked {
     type Prefix = String;
     type SubPrefix = ();
     type Suffix = String;
     type SuperSuffix = Self;
 
-    fn key(&self) -> Vec<Key> {
+    fn key(&self) -> Vec<Key<'_>> {
         let mut keys = vec![];
         match &self {
-            AssetInfo::Cw20(addr) => {
+            AssetInfoUnchecked::Cw20(addr) => {
                 keys.extend("cw20:".key());
                 keys.extend(addr.key());
             },
-            AssetInfo::Native(denom) => {
+            AssetInfoUnchecked::Native(denom) => {
                 keys.extend("native:".key());
                 keys.extend(denom.key());
             },
@@ -265,28 +269,98 @@ impl<'a> PrimaryKey<'a> for &AssetInfo {
     }
 }
 
+/// Split the raw bytes of a storage key into the asset type and the denom or
+/// contract address.
+///
+/// The bytes are expected in the format produced by `PrimaryKey::key`, i.e. the
+/// length-prefixed asset type (`native:` or `cw20:`), followed by the denom or
+/// contract address, which is not length-prefixed.
+fn split_key(value: &[u8]) -> StdResult<(String, String)> {
+    if value.len() < 2 {
+        return Err(StdError::generic_err("Could not read 2 byte length"));
+    }
+
+    let ty_len = u16::from_be_bytes([value[0], value[1]]) as usize;
+    if value.len() < 2 + ty_len {
+        return Err(StdError::generic_err("asset info key is shorter than its length prefix"));
+    }
+
+    let ty = String::from_vec(value[2..2 + ty_len].to_vec())?;
+    let inner = String::from_vec(value[2 + ty_len..].to_vec())?;
+
+    Ok((ty, inner))
+}
+
 impl KeyDeserialize for &AssetInfo {
-    const KEY_ELEMS: u16 = 1;
+    // the asset type and the denom/address are two separate key elements
+    const KEY_ELEMS: u16 = 2;
+
+    type Output = AssetInfo;
+
+    #[inline(always)]
+    fn from_vec(value: Vec<u8>) -> StdResult<Self::Output> {
+        AssetInfo::from_vec(value)
+    }
+}
+
+impl KeyDeserialize for AssetInfo {
+    const KEY_ELEMS: u16 = 2;
 
     type Output = AssetInfo;
 
     #[inline(always)]
-    fn from_vec(mut value: Vec<u8>) -> StdResult<Self::Output> {
-        // ignore length prefix
-        // we're allowed to do this because we set the key's namespace ourselves
-        // in PrimaryKey (first key)
-        value.drain(0..2);
+    fn from_vec(value: Vec<u8>) -> StdResult<Self::Output> {
+        AssetInfoUnchecked::from_vec(value).map(|info| match info {
+            AssetInfoUnchecked::Native(denom) => AssetInfo::Native(denom),
+            AssetInfoUnchecked::Cw20(contract_addr) => {
+                AssetInfo::Cw20(Addr::unchecked(contract_addr))
+            },
+        })
+    }
+}
 
-        // parse the bytes into an utf8 string
-        let s = String::from_utf8(value)?;
+impl KeyDeserialize for &AssetInfoUnchecked {
+    const KEY_ELEMS: u16 = 2;
 
-        // cast the AssetError to StdError::ParseError
-        AssetInfo::from_str(&s).map_err(|err| StdError::parse_err(type_name::<Self::Output>(), err))
+    type Output = AssetInfoUnchecked;
+
+    #[inline(always)]
+    fn from_vec(value: Vec<u8>) -> StdResult<Self::Output> {
+        AssetInfoUnchecked::from_vec(value)
     }
 }
 
-impl<'a> Prefixer<'a> for &AssetInfo {
-    fn prefix(&self) -> Vec<Key> {
+impl KeyDeserialize for AssetInfoUnchecked {
+    const KEY_ELEMS: u16 = 2;
+
+    type Output = AssetInfoUnchecked;
+
+    #[inline(always)]
+    fn from_vec(value: Vec<u8>) -> StdResult<Self::Output> {
+        let (ty, inner) = split_key(&value)?;
+
+        match ty.as_str() {
+            "native:" => Ok(AssetInfoUnchecked::Native(inner)),
+            "cw20:" => Ok(AssetInfoUnchecked::Cw20(inner)),
+            _ => {
+                // cast the AssetError to StdError::ParseError
+                let err = AssetError::InvalidAssetType {
+                    ty,
+                };
+                Err(StdError::parse_err(type_name::<Self::Output>(), err))
+            },
+        }
+    }
+}
+
+impl<'a> Prefixer<'a> for AssetInfo {
+    fn prefix(&self) -> Vec<Key<'_>> {
+        self.key()
+    }
+}
+
+impl<'a> Prefixer<'a> for AssetInfoUnchecked {
+    fn prefix(&self) -> Vec<Key<'_>> {
         self.key()
     }
 }
@@ -478,6 +552,122 @@ mod test {
         assert_eq!(items[1], (Addr::unchecked("larry"), 42069));
     }
 
+    #[test]
+    fn owned_storage_key_works() {
+        let mut deps = mock_dependencies();
+        let (key1, key2, _) = mock_keys();
+        let map: Map<AssetInfo, u64> = Map::new("map");
+
+        map.save(deps.as_mut().storage, key1.clone(), &42069).unwrap();
+        map.save(deps.as_mut().storage, key2.clone(), &69420).unwrap();
+
+        assert_eq!(map.load(deps.as_ref().storage, key1.clone()).unwrap(), 42069);
+
+        let items = map
+            .range(deps.as_ref().storage, None, None, Order::Ascending)
+            .map(|item| item.unwrap())
+            .collect::<Vec<_>>();
+
+        assert_eq!(items, vec![(key2, 69420), (key1, 42069)]);
+    }
+
+    #[test]
+    fn unchecked_storage_key_works() {
+        let mut deps = mock_dependencies();
+        let key1 = AssetInfoUnchecked::native("uusd");
+        let key2 = AssetInfoUnchecked::cw20("mock_token");
+        let map: Map<&AssetInfoUnchecked, u64> = Map::new("map");
+
+        map.save(deps.as_mut().storage, &key1, &42069).unwrap();
+        map.save(deps.as_mut().storage, &key2, &69420).unwrap();
+
+        assert_eq!(map.load(deps.as_ref().storage, &key2).unwrap(), 69420);
+
+        let items = map
+            .range(deps.as_ref().storage, None, None, Order::Ascending)
+            .map(|item| item.unwrap())
+            .collect::<Vec<_>>();
+
+        assert_eq!(items, vec![(key2, 69420), (key1, 42069)]);
+    }
+
+    #[test]
+    fn checked_and_unchecked_keys_are_identical() {
+        let info = AssetInfo::cw20(Addr::unchecked("mock_token"));
+        let info_unchecked = AssetInfoUnchecked::from(&info);
+        assert_eq!(info.joined_key(), info_unchecked.joined_key());
+
+        let info = AssetInfo::native("uusd");
+        let info_unchecked = AssetInfoUnchecked::from(&info);
+        assert_eq!(info.joined_key(), info_unchecked.joined_key());
+    }
+
+    #[test]
+    fn deserializing_key() {
+        let (key1, key2, _) = mock_keys();
+
+        assert_eq!(AssetInfo::from_vec(key1.joined_key()).unwrap(), key1);
+        assert_eq!(<&AssetInfo>::from_vec(key2.joined_key()).unwrap(), key2);
+
+        let key = AssetInfoUnchecked::cw20("mock_token");
+        assert_eq!(AssetInfoUnchecked::from_vec(key.joined_key()).unwrap(), key);
+
+        // unknown asset type
+        let bytes = ("cw721:", "galactic_punk").joined_key();
+        assert!(AssetInfo::from_vec(bytes).is_err());
+
+        // length prefix longer than the key itself
+        assert!(AssetInfo::from_vec(vec![0, 7, b'c', b'w']).is_err());
+    }
+
+    #[test]
+    fn composite_key_range_works() {
+        let mut deps = mock_dependencies();
+        let (key1, key2, key3) = mock_keys();
+        let map: Map<(AssetInfo, Addr), u64> = Map::new("map");
+
+        map.save(deps.as_mut().storage, (key1.clone(), Addr::unchecked("larry")), &1).unwrap();
+        map.save(deps.as_mut().storage, (key2.clone(), Addr::unchecked("jake")), &2).unwrap();
+        map.save(deps.as_mut().storage, (key3.clone(), Addr::unchecked("larry")), &3).unwrap();
+
+        let items = map
+            .range(deps.as_ref().storage, None, None, Order::Ascending)
+            .map(|item| item.unwrap())
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            items,
+            vec![
+                ((key2.clone(), Addr::unchecked("jake")), 2),
+                ((key3.clone(), Addr::unchecked("larry")), 3),
+                ((key1.clone(), Addr::unchecked("larry")), 1),
+            ],
+        );
+
+        let items = map
+            .prefix(key3.clone())
+            .range(deps.as_ref().storage, None, None, Order::Ascending)
+            .map(|item| item.unwrap())
+            .collect::<Vec<_>>();
+
+        assert_eq!(items, vec![(Addr::unchecked("larry"), 3)]);
+
+        // the asset info as the suffix of a composite key
+        let map: Map<(&Addr, &AssetInfo), u64> = Map::new("map2");
+        let larry = Addr::unchecked("larry");
+
+        map.save(deps.as_mut().storage, (&larry, &key1), &1).unwrap();
+        map.save(deps.as_mut().storage, (&larry, &key2), &2).unwrap();
+
+        let items = map
+            .prefix(&larry)
+            .range(deps.as_ref().storage, None, Some(Bound::exclusive(&key1)), Order::Ascending)
+            .map(|item| item.unwrap())
+            .collect::<Vec<_>>();
+
+        assert_eq!(items, vec![(key2, 2)]);
+    }
+
     #[test]
     fn triple_asset_key_works() {
         let mut deps = mock_dependencies();
@@ -1,11 +1,26 @@
-use std::{fmt, str::FromStr};
+use std::{collections::BTreeMap, fmt, str::FromStr};
 
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Api, Coin, CosmosMsg};
+use cosmwasm_std::{to_json_binary, Addr, Api, Binary, Coin, CosmosMsg, Uint128, WasmMsg};
+use cw20::Cw20ExecuteMsg;
 use cw_address_like::AddressLike;
 
 use crate::{Asset, AssetBase, AssetError, AssetInfo, AssetUnchecked};
 
+/// Specifies how CW20 tokens in an **asset list** are delivered to a contract
+/// by [`AssetList::execute_msgs`]
+#[cw_serde]
+pub enum Cw20Delivery {
+    /// Approve the contract to draw each CW20 token with an `IncreaseAllowance`
+    /// message, then execute the contract once with all native coins attached
+    /// as funds. The contract is expected to draw the tokens itself.
+    ApproveThenExecute,
+    /// Send each CW20 token to the contract with the payload as the hook
+    /// message, then, if the list contains any native coins, execute the
+    /// contract once more with all native coins attached as funds.
+    SendWithHook,
+}
+
 /// Represents a list of fungible tokens, each with a known amount
 #[cw_serde]
 pub struct AssetListBase<T: AddressLike>(Vec<AssetBase<T>>);
@@ -37,12 +52,34 @@ impl FromStr for AssetListUnchecked {
     }
 }
 
+impl AssetListUnchecked {
+    /// Parse a comma-separated string of SDK coins, e.g. `100uatom,200uosmo`,
+    /// into an `AssetListUnchecked` object. This is the format that Cosmos SDK
+    /// uses to stringify a list of native coins.
+    ///
+    /// Each coin is parsed using [`AssetUnchecked::from_sdk_string`]; see its
+    /// documentation for details. An empty string is parsed into an empty list.
+    pub fn from_sdk_string(s: &str) -> Result<Self, AssetError> {
+        if s.is_empty() {
+            return Ok(Self(vec![]));
+        }
+
+        s.split(',').map(AssetUnchecked::from_sdk_string).collect::<Result<_, _>>().map(Self)
+    }
+}
+
 impl From<AssetList> for AssetListUnchecked {
     fn from(list: AssetList) -> Self {
         Self(list.to_vec().iter().cloned().map(|asset| asset.into()).collect())
     }
 }
 
+impl From<Vec<AssetUnchecked>> for AssetListUnchecked {
+    fn from(assets: Vec<AssetUnchecked>) -> Self {
+        Self(assets)
+    }
+}
+
 impl AssetListUnchecked {
     /// Validate data contained in an _unchecked_ **asset list** instance,
     /// return a new _checked_ **asset list** instance:
@@ -112,6 +149,54 @@ impl<'a> IntoIterator for &'a AssetList {
     }
 }
 
+impl<'a> IntoIterator for &'a mut AssetList {
+    type Item = &'a mut Asset;
+    type IntoIter = std::slice::IterMut<'a, Asset>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter_mut()
+    }
+}
+
+impl IntoIterator for AssetList {
+    type Item = Asset;
+    type IntoIter = std::vec::IntoIter<Asset>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+/// Collect assets into a list, merging assets of the same kind as
+/// [`AssetList::add`] does
+///
+/// Panics if the sum of an asset's amounts overflows; use
+/// [`AssetList::try_from_iter`] to handle this case.
+impl FromIterator<Asset> for AssetList {
+    fn from_iter<I: IntoIterator<Item = Asset>>(iter: I) -> Self {
+        let mut list = AssetList::new();
+        list.extend(iter);
+        list
+    }
+}
+
+/// Add assets to the list, merging assets of the same kind as
+/// [`AssetList::add`] does
+///
+/// Panics if the sum of an asset's amounts overflows; use
+/// [`AssetList::try_extend`] to handle this case.
+impl Extend<Asset> for AssetList {
+    fn extend<I: IntoIterator<Item = Asset>>(&mut self, iter: I) {
+        self.try_extend(iter).expect("overflow when adding assets to asset list");
+    }
+}
+
+impl<'a> Extend<&'a Asset> for AssetList {
+    fn extend<I: IntoIterator<Item = &'a Asset>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().cloned());
+    }
+}
+
 impl From<Vec<Asset>> for AssetList {
     fn from(vec: Vec<Asset>) -> Self {
         Self(vec)
@@ -148,6 +233,22 @@ impl From<&[Coin]> for AssetList {
     }
 }
 
+impl TryFrom<&AssetList> for Vec<Coin> {
+    type Error = AssetError;
+
+    fn try_from(list: &AssetList) -> Result<Self, Self::Error> {
+        list.to_coins()
+    }
+}
+
+impl TryFrom<AssetList> for Vec<Coin> {
+    type Error = AssetError;
+
+    fn try_from(list: AssetList) -> Result<Self, Self::Error> {
+        list.to_coins()
+    }
+}
+
 impl AssetList {
     /// Create a new, empty asset list
     ///
@@ -207,6 +308,36 @@ impl AssetList {
         self.0.is_empty()
     }
 
+    /// Return an iterator over the assets in the list
+    ///
+    /// ```rust
+    /// use cw_asset::{Asset, AssetList};
+    ///
+    /// let list =
+    ///     AssetList::from(vec![Asset::native("uluna", 12345u128), Asset::native("uusd", 67890u128)]);
+    ///
+    /// let denoms: Vec<String> = list.iter().map(|asset| asset.info.inner()).collect();
+    /// ```
+    pub fn iter(&self) -> std::slice::Iter<'_, Asset> {
+        self.0.iter()
+    }
+
+    /// Return an iterator that allows modifying each asset in the list
+    ///
+    /// ```rust
+    /// use cw_asset::{Asset, AssetList};
+    ///
+    /// let mut list =
+    ///     AssetList::from(vec![Asset::native("uluna", 12345u128), Asset::native("uusd", 67890u128)]);
+    ///
+    /// for asset in list.iter_mut() {
+    ///     asset.amount = asset.amount.multiply_ratio(1u128, 2u128);
+    /// }
+    /// ```
+    pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, Asset> {
+        self.0.iter_mut()
+    }
+
     /// Find an asset in the list that matches the provided asset info
     ///
     /// Return `Some(&asset)` if found, where `&asset` is a reference to the
@@ -266,6 +397,41 @@ impl AssetList {
         self
     }
 
+    /// Remove the asset that matches the provided asset info from the list
+    ///
+    /// Return `Some(asset)` if found, where `asset` is the asset removed; `None`
+    /// if not found. The order of the remaining assets is preserved.
+    ///
+    /// ```rust
+    /// use cw_asset::{Asset, AssetInfo, AssetList};
+    ///
+    /// let mut list =
+    ///     AssetList::from(vec![Asset::native("uluna", 12345u128), Asset::native("uusd", 67890u128)]);
+    ///
+    /// let removed = list.remove(&AssetInfo::native("uluna")); // should be 12345 uluna
+    /// let len = list.len(); // should be one
+    /// ```
+    pub fn remove(&mut self, info: &AssetInfo) -> Option<Asset> {
+        let index = self.0.iter().position(|asset| asset.info == *info)?;
+        Some(self.0.remove(index))
+    }
+
+    /// Retain only the assets for which the predicate returns `true`
+    ///
+    /// ```rust
+    /// use cw_asset::{Asset, AssetList};
+    ///
+    /// let mut list =
+    ///     AssetList::from(vec![Asset::native("uluna", 12345u128), Asset::native("uusd", 67890u128)]);
+    ///
+    /// list.retain(|asset| asset.amount.u128() > 50000);
+    /// let len = list.len(); // should be one
+    /// ```
+    pub fn retain<F: FnMut(&Asset) -> bool>(&mut self, f: F) -> &mut Self {
+        self.0.retain(f);
+        self
+    }
+
     /// Add a new asset to the list
     ///
     /// If asset of the same kind already exists in the list, then increment its
@@ -335,6 +501,55 @@ impl AssetList {
         Ok(self)
     }
 
+    /// Add assets to the list, merging assets of the same kind as
+    /// [`AssetList::add`] does
+    ///
+    /// This is the fallible counterpart of the `Extend` implementation. Returns
+    /// error if the sum of an asset's amounts overflows, in which case the
+    /// assets before the overflowing one have already been added.
+    ///
+    /// ```rust
+    /// use cw_asset::{Asset, AssetList};
+    ///
+    /// let mut list = AssetList::from(vec![Asset::native("uluna", u128::MAX)]);
+    ///
+    /// let res = list.try_extend(vec![Asset::native("uluna", 1u128)]);
+    /// assert!(res.is_err());
+    /// ```
+    pub fn try_extend<I: IntoIterator<Item = Asset>>(
+        &mut self,
+        iter: I,
+    ) -> Result<&mut Self, AssetError> {
+        for asset in iter {
+            self.add(&asset)?;
+        }
+        Ok(self)
+    }
+
+    /// Collect assets into a new list, merging assets of the same kind as
+    /// [`AssetList::add`] does
+    ///
+    /// This is the fallible counterpart of the `FromIterator` implementation.
+    /// Returns error if the sum of an asset's amounts overflows.
+    ///
+    /// ```rust
+    /// use cw_asset::{Asset, AssetInfo, AssetList};
+    ///
+    /// let list = AssetList::try_from_iter(vec![
+    ///     Asset::native("uluna", 12345u128),
+    ///     Asset::native("uusd", 67890u128),
+    ///     Asset::native("uluna", 11111u128),
+    /// ])
+    /// .unwrap();
+    ///
+    /// let len = list.len(); // should be two
+    /// ```
+    pub fn try_from_iter<I: IntoIterator<Item = Asset>>(iter: I) -> Result<Self, AssetError> {
+        let mut list = AssetList::new();
+        list.try_extend(iter)?;
+        Ok(list)
+    }
+
     /// Deduct an asset from the list
     ///
     /// The asset of the same kind and equal or greater amount must already
@@ -406,6 +621,29 @@ impl AssetList {
         Ok(self)
     }
 
+    /// Stringify the list into the comma-separated `{amount}{denom}` format
+    /// used by the Cosmos SDK, e.g. `100uatom,200uosmo`. This is the inverse
+    /// of [`AssetListUnchecked::from_sdk_string`].
+    ///
+    /// NOTE: Only works if every asset in the list is a native coin. Returns
+    /// error if the list contains any CW20 token.
+    ///
+    /// ```rust
+    /// use cw_asset::{Asset, AssetList};
+    ///
+    /// let list =
+    ///     AssetList::from(vec![Asset::native("uatom", 100u128), Asset::native("uosmo", 200u128)]);
+    ///
+    /// let s = list.to_sdk_string().unwrap(); // should be `100uatom,200uosmo`
+    /// ```
+    pub fn to_sdk_string(&self) -> Result<String, AssetError> {
+        self.0
+            .iter()
+            .map(|asset| asset.to_sdk_string())
+            .collect::<Result<Vec<_>, _>>()
+            .map(|strs| strs.join(","))
+    }
+
     /// Generate a transfer messages for every asset in the list
     ///
     /// ```rust
@@ -424,6 +662,165 @@ impl AssetList {
     ) -> Result<Vec<CosmosMsg>, AssetError> {
         self.0.iter().map(|asset| asset.transfer_msg(to.clone())).collect()
     }
+
+    /// Generate a message for every asset in the list that deposits it into a
+    /// contract, along with a binary payload
+    ///
+    /// See [`Asset::deposit_msg`] for how CW20 tokens and native coins are
+    /// deposited.
+    ///
+    /// ```rust
+    /// use cosmwasm_std::{Addr, Binary, Response};
+    /// use cw_asset::{AssetError, AssetList};
+    ///
+    /// fn forward_assets(
+    ///     list: &AssetList,
+    ///     vault_addr: &Addr,
+    ///     msg: Binary,
+    /// ) -> Result<Response, AssetError> {
+    ///     let msgs = list.deposit_msgs(vault_addr, msg)?;
+    ///
+    ///     Ok(Response::new().add_messages(msgs).add_attribute("assets_deposited", list.to_string()))
+    /// }
+    /// ```
+    pub fn deposit_msgs<A: Into<String> + Clone>(
+        &self,
+        contract: A,
+        msg: Binary,
+    ) -> Result<Vec<CosmosMsg>, AssetError> {
+        self.0.iter().map(|asset| asset.deposit_msg(contract.clone(), msg.clone())).collect()
+    }
+
+    /// Split the list into the native coins, which can be attached as funds to
+    /// a message, and the remaining CW20 tokens
+    ///
+    /// The coins are sorted by denom, coins of the same denom are merged, and
+    /// coins of zero amount are dropped, as required by the Cosmos SDK for
+    /// funds. Returns an overflow error if merged amounts exceed `Uint128::MAX`.
+    ///
+    /// ```rust
+    /// use cosmwasm_std::{Addr, Coin};
+    /// use cw_asset::{Asset, AssetList};
+    ///
+    /// let list = AssetList::from(vec![
+    ///     Asset::native("uosmo", 100u128),
+    ///     Asset::cw20(Addr::unchecked("mock_token"), 200u128),
+    ///     Asset::native("uatom", 300u128),
+    /// ]);
+    ///
+    /// let (funds, cw20s) = list.split_natives().unwrap();
+    /// assert_eq!(funds, vec![Coin::new(300u128, "uatom"), Coin::new(100u128, "uosmo")]);
+    /// assert_eq!(cw20s, AssetList::from(vec![Asset::cw20(Addr::unchecked("mock_token"), 200u128)]));
+    /// ```
+    pub fn split_natives(&self) -> Result<(Vec<Coin>, AssetList), AssetError> {
+        let mut amounts: BTreeMap<&str, Uint128> = BTreeMap::new();
+        let mut cw20s = vec![];
+        for asset in &self.0 {
+            match &asset.info {
+                AssetInfo::Native(denom) => {
+                    let amount = amounts.entry(denom).or_default();
+                    *amount = amount.checked_add(asset.amount)?;
+                },
+                AssetInfo::Cw20(_) => cw20s.push(asset.clone()),
+            }
+        }
+
+        let funds = amounts
+            .into_iter()
+            .filter(|(_, amount)| !amount.is_zero())
+            .map(|(denom, amount)| Coin::new(amount, denom))
+            .collect();
+
+        Ok((funds, AssetList::from(cw20s)))
+    }
+
+    /// Convert the list into native coins, sorted by denom, with coins of the
+    /// same denom merged and coins of zero amount dropped, e.g. to be attached
+    /// as funds to a message
+    ///
+    /// NOTE: Only works if every asset in the list is a native coin. Returns
+    /// error if the list contains any CW20 token. To keep the CW20 tokens
+    /// instead, use [`AssetList::split_natives`].
+    ///
+    /// ```rust
+    /// use cosmwasm_std::Coin;
+    /// use cw_asset::{Asset, AssetList};
+    ///
+    /// let list =
+    ///     AssetList::from(vec![Asset::native("uosmo", 100u128), Asset::native("uatom", 200u128)]);
+    ///
+    /// let funds = list.to_coins().unwrap();
+    /// assert_eq!(funds, vec![Coin::new(200u128, "uatom"), Coin::new(100u128, "uosmo")]);
+    /// ```
+    pub fn to_coins(&self) -> Result<Vec<Coin>, AssetError> {
+        let (funds, cw20s) = self.split_natives()?;
+        match cw20s.0.first() {
+            Some(asset) => Err(AssetError::CannotCastToStdCoin {
+                asset: asset.to_string(),
+            }),
+            None => Ok(funds),
+        }
+    }
+
+    /// Generate messages that execute a contract with all assets in the list
+    ///
+    /// All native coins are attached as funds to a single `WasmMsg::Execute`
+    /// carrying the payload, sorted and merged as described in
+    /// [`AssetList::split_natives`]; CW20 tokens are delivered according to the
+    /// specified [`Cw20Delivery`] strategy.
+    ///
+    /// ```rust
+    /// use cosmwasm_std::{Addr, Binary, Response};
+    /// use cw_asset::{AssetError, AssetList, Cw20Delivery};
+    ///
+    /// fn provide_liquidity(
+    ///     list: &AssetList,
+    ///     pool_addr: &Addr,
+    ///     msg: Binary,
+    /// ) -> Result<Response, AssetError> {
+    ///     let msgs = list.execute_msgs(pool_addr, msg, Cw20Delivery::ApproveThenExecute)?;
+    ///
+    ///     Ok(Response::new().add_messages(msgs).add_attribute("assets_provided", list.to_string()))
+    /// }
+    /// ```
+    pub fn execute_msgs<A: Into<String>>(
+        &self,
+        contract: A,
+        msg: Binary,
+        delivery: Cw20Delivery,
+    ) -> Result<Vec<CosmosMsg>, AssetError> {
+        let contract = contract.into();
+        let (funds, cw20s) = self.split_natives()?;
+
+        let mut msgs = vec![];
+
+        for asset in &cw20s.0 {
+            if let AssetInfo::Cw20(contract_addr) = &asset.info {
+                msgs.push(match delivery {
+                    Cw20Delivery::ApproveThenExecute => CosmosMsg::Wasm(WasmMsg::Execute {
+                        contract_addr: contract_addr.into(),
+                        msg: to_json_binary(&Cw20ExecuteMsg::IncreaseAllowance {
+                            spender: contract.clone(),
+                            amount: asset.amount,
+                            expires: None,
+                        })?,
+                        funds: vec![],
+                    }),
+                    Cw20Delivery::SendWithHook => asset.send_msg(&contract, msg.clone())?,
+                });
+            }
+        }
+
+        if delivery == Cw20Delivery::ApproveThenExecute || !funds.is_empty() {
+            msgs.push(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: contract,
+                msg,
+                funds,
+            }));
+        }
+
+        Ok(msgs)
+    }
 }
 
 //------------------------------------------------------------------------------
@@ -513,6 +910,58 @@ mod tests {
         assert_eq!(list.to_string(), String::from("[]"));
     }
 
+    #[test]
+    fn from_sdk_string() {
+        let s = "";
+        assert_eq!(
+            AssetListUnchecked::from_sdk_string(s).unwrap(),
+            AssetListBase::<String>(vec![])
+        );
+
+        let s = "100uatom,200uosmo";
+        assert_eq!(
+            AssetListUnchecked::from_sdk_string(s).unwrap(),
+            AssetListBase(vec![
+                AssetUnchecked::native("uatom", 100u128),
+                AssetUnchecked::native("uosmo", 200u128),
+            ]),
+        );
+
+        let s = "100uatom,123";
+        assert_eq!(
+            AssetListUnchecked::from_sdk_string(s),
+            Err(AssetError::InvalidSdkCoin {
+                coin_str: "123".into(),
+            }),
+        );
+
+        let s = "100uatom,340282366920938463463374607431768211456uosmo";
+        assert_eq!(
+            AssetListUnchecked::from_sdk_string(s),
+            Err(AssetError::InvalidSdkCoin {
+                coin_str: "340282366920938463463374607431768211456uosmo".into(),
+            }),
+        );
+    }
+
+    #[test]
+    fn to_sdk_string() {
+        let list =
+            AssetList::from(vec![Asset::native("uatom", 100u128), Asset::native("uosmo", 200u128)]);
+        assert_eq!(list.to_sdk_string().unwrap(), String::from("100uatom,200uosmo"));
+
+        let list = AssetList::new();
+        assert_eq!(list.to_sdk_string().unwrap(), String::new());
+
+        let list = mock_list();
+        assert_eq!(
+            list.to_sdk_string(),
+            Err(AssetError::CannotCastToStdCoin {
+                asset: "cw20:cosmos1c4k24jzduc365kywrsvf5ujz4ya6mwymy8vq4q:88888".into(),
+            }),
+        );
+    }
+
     #[test]
     fn indexing() {
         let list = mock_list();
@@ -535,6 +984,80 @@ mod tests {
         );
     }
 
+    #[test]
+    fn iterating_mutably() {
+        let mut list = mock_list();
+
+        for asset in &mut list {
+            asset.amount = asset.amount.multiply_ratio(1u128, 2u128);
+        }
+        list.iter_mut().for_each(|asset| asset.amount += Uint128::one());
+        assert_eq!(
+            list,
+            AssetList::from(vec![
+                Asset::native("uusd", 34711u128),
+                Asset::new(mock_token(), 44445u128),
+            ]),
+        );
+
+        let assets: Vec<Asset> = list.clone().into_iter().collect();
+        assert_eq!(assets, list.to_vec());
+        assert_eq!(list.iter().count(), 2);
+    }
+
+    #[test]
+    fn collecting() {
+        let list: AssetList = vec![
+            Asset::native("uusd", 69420u128),
+            Asset::native("uluna", 0u128),
+            Asset::new(mock_token(), 80000u128),
+            Asset::new(mock_token(), 8888u128),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(list, mock_list());
+
+        let mut list = AssetList::from(vec![Asset::native("uusd", 1u128)]);
+        list.extend(&mock_list().to_vec());
+        list.extend(vec![Asset::native("uluna", 12345u128)]);
+        assert_eq!(
+            list,
+            AssetList::from(vec![
+                Asset::native("uusd", 69421u128),
+                Asset::new(mock_token(), 88888u128),
+                Asset::native("uluna", 12345u128),
+            ]),
+        );
+
+        let assets = vec![Asset::native("uusd", u128::MAX), Asset::native("uusd", 1u128)];
+        assert!(matches!(AssetList::try_from_iter(assets), Err(AssetError::Overflow(_))));
+
+        let mut list = mock_list();
+        let res =
+            list.try_extend(vec![Asset::native("uluna", 1u128), Asset::native("uusd", u128::MAX)]);
+        assert!(matches!(res, Err(AssetError::Overflow(_))));
+    }
+
+    #[test]
+    #[should_panic(expected = "overflow when adding assets to asset list")]
+    fn collecting_overflow() {
+        let _: AssetList = vec![Asset::native("uusd", u128::MAX), Asset::native("uusd", 1u128)]
+            .into_iter()
+            .collect();
+    }
+
+    #[test]
+    fn removing_and_retaining() {
+        let mut list = mock_list();
+        assert_eq!(list.remove(&uluna()), None);
+        assert_eq!(list.remove(&uusd()), Some(Asset::native("uusd", 69420u128)));
+        assert_eq!(list, AssetList::from(vec![Asset::new(mock_token(), 88888u128)]));
+
+        let mut list = mock_list();
+        list.retain(|asset| matches!(asset.info, AssetInfo::Native(_)));
+        assert_eq!(list, AssetList::from(vec![Asset::native("uusd", 69420u128)]));
+    }
+
     #[test]
     fn checking() {
         let api = MockApi::default().with_prefix("cosmos");
@@ -673,5 +1196,141 @@ mod tests {
                 }),
             ],
         );
+
+        let bin_msg = to_json_binary(&"deposit").unwrap();
+        let msgs = list.deposit_msgs("vault", bin_msg.clone()).unwrap();
+        assert_eq!(
+            msgs,
+            vec![
+                CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: String::from("vault"),
+                    msg: bin_msg.clone(),
+                    funds: vec![Coin::new(69420u128, "uusd")]
+                }),
+                CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: String::from("cosmos1c4k24jzduc365kywrsvf5ujz4ya6mwymy8vq4q"),
+                    msg: to_json_binary(&Cw20ExecuteMsg::Send {
+                        contract: String::from("vault"),
+                        amount: Uint128::new(88888),
+                        msg: bin_msg
+                    })
+                    .unwrap(),
+                    funds: vec![]
+                }),
+            ],
+        );
+    }
+
+    #[test]
+    fn splitting_natives() {
+        let list = AssetList::from(vec![
+            Asset::native("uusd", 69420u128),
+            Asset::new(mock_token(), 88888u128),
+            Asset::native("uluna", 12345u128),
+            Asset::native("uusd", 1u128),
+        ]);
+        let (funds, cw20s) = list.split_natives().unwrap();
+        assert_eq!(funds, vec![Coin::new(12345u128, "uluna"), Coin::new(69421u128, "uusd")]);
+        assert_eq!(cw20s, AssetList::from(vec![Asset::new(mock_token(), 88888u128)]));
+
+        let list = AssetList::from(vec![
+            Asset::native("uosmo", 1u128),
+            Asset::native("uatom", 0u128),
+            Asset::native("uusd", 0u128),
+            Asset::native("uusd", 0u128),
+            Asset::native("uatom", 2u128),
+        ]);
+        let (funds, _) = list.split_natives().unwrap();
+        assert_eq!(funds, vec![Coin::new(2u128, "uatom"), Coin::new(1u128, "uosmo")]);
+        assert_eq!(list.to_coins().unwrap(), funds);
+
+        let (funds, cw20s) = AssetList::new().split_natives().unwrap();
+        assert!(funds.is_empty());
+        assert_eq!(cw20s, AssetList::new());
+
+        let list =
+            AssetList::from(vec![Asset::native("uusd", u128::MAX), Asset::native("uusd", 1u128)]);
+        assert_eq!(list.split_natives(), Err(OverflowError::new(OverflowOperation::Add).into()));
+    }
+
+    #[test]
+    fn converting_to_coins() {
+        let list = AssetList::from(vec![
+            Asset::native("uusd", 69420u128),
+            Asset::native("uluna", 12345u128),
+            Asset::native("uluna", 1u128),
+        ]);
+        let expected = vec![Coin::new(12346u128, "uluna"), Coin::new(69420u128, "uusd")];
+        assert_eq!(list.to_coins().unwrap(), expected);
+        assert_eq!(Vec::<Coin>::try_from(&list).unwrap(), expected);
+        assert_eq!(Vec::<Coin>::try_from(list).unwrap(), expected);
+
+        let err = mock_list().to_coins();
+        assert_eq!(
+            err,
+            Err(AssetError::CannotCastToStdCoin {
+                asset: "cw20:cosmos1c4k24jzduc365kywrsvf5ujz4ya6mwymy8vq4q:88888".into(),
+            }),
+        );
+    }
+
+    #[test]
+    fn creating_execute_messages() {
+        let list = mock_list();
+        let bin_msg = to_json_binary(&"provide").unwrap();
+
+        let msgs =
+            list.execute_msgs("pool", bin_msg.clone(), Cw20Delivery::ApproveThenExecute).unwrap();
+        assert_eq!(
+            msgs,
+            vec![
+                CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: String::from("cosmos1c4k24jzduc365kywrsvf5ujz4ya6mwymy8vq4q"),
+                    msg: to_json_binary(&Cw20ExecuteMsg::IncreaseAllowance {
+                        spender: String::from("pool"),
+                        amount: Uint128::new(88888),
+                        expires: None
+                    })
+                    .unwrap(),
+                    funds: vec![]
+                }),
+                CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: String::from("pool"),
+                    msg: bin_msg.clone(),
+                    funds: vec![Coin::new(69420u128, "uusd")]
+                }),
+            ],
+        );
+
+        let msgs = list.execute_msgs("pool", bin_msg.clone(), Cw20Delivery::SendWithHook).unwrap();
+        assert_eq!(
+            msgs,
+            vec![
+                CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: String::from("cosmos1c4k24jzduc365kywrsvf5ujz4ya6mwymy8vq4q"),
+                    msg: to_json_binary(&Cw20ExecuteMsg::Send {
+                        contract: String::from("pool"),
+                        amount: Uint128::new(88888),
+                        msg: bin_msg.clone()
+                    })
+                    .unwrap(),
+                    funds: vec![]
+                }),
+                CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: String::from("pool"),
+                    msg: bin_msg.clone(),
+                    funds: vec![Coin::new(69420u128, "uusd")]
+                }),
+            ],
+        );
+
+        // without native coins, the contract is only executed through the hooks
+        let cw20s = AssetList::from(vec![Asset::new(mock_token(), 88888u128)]);
+        let msgs = cw20s.execute_msgs("pool", bin_msg.clone(), Cw20Delivery::SendWithHook).unwrap();
+        assert_eq!(msgs.len(), 1);
+
+        // ...but is always executed when approving
+        let msgs = cw20s.execute_msgs("pool", bin_msg, Cw20Delivery::ApproveThenExecute).unwrap();
+        assert_eq!(msgs.len(), 2);
     }
 }
@@ -0,0 +1,351 @@
+use std::any::type_name;
+
+use cosmwasm_schema::serde::{de::DeserializeOwned, Serialize};
+use cosmwasm_std::{Addr, Order, StdError, StdResult, Storage};
+use cw_storage_plus::{Key, KeyDeserialize, Map, Prefixer, PrimaryKey};
+
+use crate::{AssetError, AssetInfo};
+
+/// The one-byte type tag of native coins in the compact key encoding
+pub const NATIVE_TAG: u8 = 0;
+
+/// The one-byte type tag of CW20 tokens in the compact key encoding
+pub const CW20_TAG: u8 = 1;
+
+/// Wraps an **asset info** instance so that it can be used as a storage key with
+/// a compact binary encoding.
+///
+/// The default key encoding of [`AssetInfo`] writes the asset type as the ASCII
+/// string `native:` or `cw20:`. This wrapper instead writes a one-byte type tag
+/// ([`NATIVE_TAG`] or [`CW20_TAG`]), followed by the raw bytes of the denom or
+/// contract address, which saves 6 bytes (native) or 4 bytes (CW20) per key.
+///
+/// As with the default encoding, the tag is a separate key element, so a map
+/// can be prefixed by the tag to iterate over all assets of one type:
+///
+/// ```rust
+/// use cosmwasm_std::{testing::MockStorage, Addr, Order, StdResult};
+/// use cw_asset::{AssetInfo, CompactAssetInfo, NATIVE_TAG};
+/// use cw_storage_plus::Map;
+///
+/// const BALANCES: Map<CompactAssetInfo, u128> = Map::new("balances");
+///
+/// let mut storage = MockStorage::default();
+/// let info = AssetInfo::native("uatom");
+/// BALANCES.save(&mut storage, info.clone().into(), &12345).unwrap();
+///
+/// let natives = BALANCES
+///     .prefix(NATIVE_TAG)
+///     .range(&storage, None, None, Order::Ascending)
+///     .collect::<StdResult<Vec<(String, u128)>>>()
+///     .unwrap(); // should be `[("uatom", 12345)]`
+/// ```
+///
+/// Keys are deserialized into plain [`AssetInfo`] instances.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CompactAssetInfo(pub AssetInfo);
+
+impl From<AssetInfo> for CompactAssetInfo {
+    fn from(info: AssetInfo) -> Self {
+        Self(info)
+    }
+}
+
+impl From<&AssetInfo> for CompactAssetInfo {
+    fn from(info: &AssetInfo) -> Self {
+        Self(info.clone())
+    }
+}
+
+impl From<CompactAssetInfo> for AssetInfo {
+    fn from(key: CompactAssetInfo) -> Self {
+        key.0
+    }
+}
+
+impl<'a> PrimaryKey<'a> for CompactAssetInfo {
+    type Prefix = u8;
+    type SubPrefix = ();
+    type Suffix = String;
+    type SuperSuffix = Self;
+
+    fn key(&self) -> Vec<Key<'_>> {
+        match &self.0 {
+            AssetInfo::Native(denom) => vec![Key::Val8([NATIVE_TAG]), Key::Ref(denom.as_bytes())],
+            AssetInfo::Cw20(addr) => vec![Key::Val8([CW20_TAG]), Key::Ref(addr.as_bytes())],
+        }
+    }
+}
+
+impl<'a> Prefixer<'a> for CompactAssetInfo {
+    fn prefix(&self) -> Vec<Key<'_>> {
+        self.key()
+    }
+}
+
+impl KeyDeserialize for CompactAssetInfo {
+    // the tag and the denom/address are two separate key elements
+    const KEY_ELEMS: u16 = 2;
+
+    type Output = AssetInfo;
+
+    #[inline(always)]
+    fn from_vec(value: Vec<u8>) -> StdResult<Self::Output> {
+        // the tag is length-prefixed, which is always [0, 1] for a single byte
+        if value.len() < 3 || value[..2] != [0, 1] {
+            return Err(StdError::generic_err("invalid compact asset info key"));
+        }
+
+        let inner = String::from_vec(value[3..].to_vec())?;
+
+        match value[2] {
+            NATIVE_TAG => Ok(AssetInfo::Native(inner)),
+            CW20_TAG => Ok(AssetInfo::Cw20(Addr::unchecked(inner))),
+            tag => {
+                // cast the AssetError to StdError::ParseError
+                let err = AssetError::InvalidAssetType {
+                    ty: tag.to_string(),
+                };
+                Err(StdError::parse_err(type_name::<Self::Output>(), err))
+            },
+        }
+    }
+}
+
+impl KeyDeserialize for &CompactAssetInfo {
+    const KEY_ELEMS: u16 = 2;
+
+    type Output = AssetInfo;
+
+    #[inline(always)]
+    fn from_vec(value: Vec<u8>) -> StdResult<Self::Output> {
+        CompactAssetInfo::from_vec(value)
+    }
+}
+
+/// Move up to `limit` entries of a map keyed by [`AssetInfo`] in the default
+/// encoding into a map keyed by [`CompactAssetInfo`].
+///
+/// Each migrated entry is removed from the old map, so the function can simply
+/// be invoked repeatedly (e.g. once per migration transaction) until it returns
+/// a number smaller than `limit`, which indicates the migration is complete.
+///
+/// Use distinct namespaces for the two maps. The new map may reuse the old
+/// map's namespace, as the old entries are found by prefixing the old map by
+/// their `cw20:` or `native:` type, which never matches a key in the compact
+/// encoding; but then the new map can't be iterated over until the migration
+/// is complete, as the remaining old keys fail to deserialize. Loading single
+/// entries is unaffected either way.
+///
+/// ```rust
+/// use cosmwasm_std::{StdResult, Storage};
+/// use cw_asset::{migrate_to_compact_keys, AssetInfo, CompactAssetInfo};
+/// use cw_storage_plus::Map;
+///
+/// const OLD_BALANCES: Map<&AssetInfo, u128> = Map::new("balances");
+/// const BALANCES: Map<CompactAssetInfo, u128> = Map::new("balances_v2");
+///
+/// fn migrate_batch(storage: &mut dyn Storage) -> StdResult<bool> {
+///     let limit = 100;
+///     let migrated = migrate_to_compact_keys(storage, &OLD_BALANCES, &BALANCES, limit)?;
+///     Ok(migrated < limit) // whether the migration is complete
+/// }
+/// ```
+pub fn migrate_to_compact_keys<V>(
+    storage: &mut dyn Storage,
+    old: &Map<&AssetInfo, V>,
+    new: &Map<CompactAssetInfo, V>,
+    limit: usize,
+) -> StdResult<usize>
+where
+    V: Serialize + DeserializeOwned,
+{
+    let mut entries = vec![];
+    for (prefix, tag) in [("cw20:", CW20_TAG), ("native:", NATIVE_TAG)] {
+        if entries.len() >= limit {
+            break;
+        }
+
+        let batch = old
+            .prefix(prefix.to_string())
+            .range(storage, None, None, Order::Ascending)
+            .take(limit - entries.len())
+            .collect::<StdResult<Vec<_>>>()?;
+
+        entries.extend(batch.into_iter().map(|(inner, value)| {
+            let info = match tag {
+                NATIVE_TAG => AssetInfo::Native(inner),
+                _ => AssetInfo::Cw20(Addr::unchecked(inner)),
+            };
+            (info, value)
+        }));
+    }
+
+    for (info, value) in &entries {
+        old.remove(storage, info);
+        new.save(storage, info.into(), value)?;
+    }
+
+    Ok(entries.len())
+}
+
+//------------------------------------------------------------------------------
+// Tests
+//------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::testing::MockStorage;
+    use cw_storage_plus::Bound;
+
+    use super::*;
+
+    fn mock_keys() -> Vec<AssetInfo> {
+        vec![
+            AssetInfo::native("uatom"),
+            AssetInfo::native("uosmo"),
+            AssetInfo::cw20(Addr::unchecked("mock_token")),
+            AssetInfo::cw20(Addr::unchecked("mock_token2")),
+        ]
+    }
+
+    #[test]
+    fn encoding() {
+        let key = CompactAssetInfo::from(AssetInfo::native("uatom"));
+        assert_eq!(key.joined_key(), b"\x00\x01\x00uatom".to_vec());
+
+        let key = CompactAssetInfo::from(AssetInfo::cw20(Addr::unchecked("mock_token")));
+        assert_eq!(key.joined_key(), b"\x00\x01\x01mock_token".to_vec());
+
+        // shorter than the default encoding
+        let info = AssetInfo::native("uatom");
+        assert_eq!(info.joined_key().len() - CompactAssetInfo::from(&info).joined_key().len(), 6);
+    }
+
+    #[test]
+    fn deserializing() {
+        for info in mock_keys() {
+            let key = CompactAssetInfo::from(&info);
+            assert_eq!(CompactAssetInfo::from_vec(key.joined_key()).unwrap(), info);
+        }
+
+        assert!(CompactAssetInfo::from_vec(b"\x00\x01\x02mock_nft".to_vec()).is_err());
+        assert!(CompactAssetInfo::from_vec(b"\x00\x01".to_vec()).is_err());
+        assert!(CompactAssetInfo::from_vec(AssetInfo::native("uatom").joined_key()).is_err());
+    }
+
+    #[test]
+    fn storage_key_works() {
+        let mut storage = MockStorage::default();
+        let map: Map<CompactAssetInfo, u64> = Map::new("map");
+
+        for (i, info) in mock_keys().into_iter().enumerate() {
+            map.save(&mut storage, info.into(), &(i as u64)).unwrap();
+        }
+
+        let items = map
+            .range(&storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()
+            .unwrap();
+        assert_eq!(
+            items,
+            vec![
+                (AssetInfo::native("uatom"), 0),
+                (AssetInfo::native("uosmo"), 1),
+                (AssetInfo::cw20(Addr::unchecked("mock_token")), 2),
+                (AssetInfo::cw20(Addr::unchecked("mock_token2")), 3),
+            ],
+        );
+
+        let items = map
+            .prefix(CW20_TAG)
+            .range(
+                &storage,
+                Some(Bound::exclusive("mock_token".to_string())),
+                None,
+                Order::Ascending,
+            )
+            .collect::<StdResult<Vec<_>>>()
+            .unwrap();
+        assert_eq!(items, vec![("mock_token2".to_string(), 3)]);
+    }
+
+    #[test]
+    fn composite_key_works() {
+        let mut storage = MockStorage::default();
+        let map: Map<(CompactAssetInfo, &Addr), u64> = Map::new("map");
+        let larry = Addr::unchecked("larry");
+
+        for (i, info) in mock_keys().into_iter().enumerate() {
+            map.save(&mut storage, (info.into(), &larry), &(i as u64)).unwrap();
+        }
+
+        let items = map
+            .range(&storage, None, None, Order::Descending)
+            .take(1)
+            .collect::<StdResult<Vec<_>>>()
+            .unwrap();
+        assert_eq!(items, vec![((AssetInfo::cw20(Addr::unchecked("mock_token2")), larry), 3)]);
+    }
+
+    #[test]
+    fn migrating() {
+        let mut storage = MockStorage::default();
+        let old: Map<&AssetInfo, u64> = Map::new("map");
+        let new: Map<CompactAssetInfo, u64> = Map::new("map");
+
+        for (i, info) in mock_keys().iter().enumerate() {
+            old.save(&mut storage, info, &(i as u64)).unwrap();
+        }
+
+        assert_eq!(migrate_to_compact_keys(&mut storage, &old, &new, 3).unwrap(), 3);
+        assert_eq!(migrate_to_compact_keys(&mut storage, &old, &new, 3).unwrap(), 1);
+        assert_eq!(migrate_to_compact_keys(&mut storage, &old, &new, 3).unwrap(), 0);
+
+        for (i, info) in mock_keys().iter().enumerate() {
+            assert!(!old.has(&storage, info));
+            assert_eq!(new.load(&storage, info.into()).unwrap(), i as u64);
+        }
+
+        let count = new.range(&storage, None, None, Order::Ascending).count();
+        assert_eq!(count, mock_keys().len());
+    }
+
+    #[test]
+    fn ranging_mid_migration() {
+        let old: Map<&AssetInfo, u64> = Map::new("map");
+        let shared: Map<CompactAssetInfo, u64> = Map::new("map");
+        let distinct: Map<CompactAssetInfo, u64> = Map::new("map_v2");
+
+        let mock_storage = || {
+            let mut storage = MockStorage::default();
+            for (i, info) in mock_keys().iter().enumerate() {
+                old.save(&mut storage, info, &(i as u64)).unwrap();
+            }
+            storage
+        };
+        let mut storage = mock_storage();
+        let mut distinct_storage = mock_storage();
+
+        // with a distinct namespace, the migrated entries can be iterated over
+        migrate_to_compact_keys(&mut distinct_storage, &old, &distinct, 1).unwrap();
+        let entries = distinct
+            .range(&distinct_storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()
+            .unwrap();
+        assert_eq!(entries.len(), 1);
+
+        // with a shared namespace, iterating reaches the remaining old keys,
+        // while loading single entries still works
+        migrate_to_compact_keys(&mut storage, &old, &shared, 1).unwrap();
+        let (info, value) = &entries[0];
+        assert_eq!(shared.load(&storage, info.into()).unwrap(), *value);
+        let result =
+            shared.range(&storage, None, None, Order::Ascending).collect::<StdResult<Vec<_>>>();
+        assert!(result.is_err());
+
+        while migrate_to_compact_keys(&mut storage, &old, &shared, 1).unwrap() == 1 {}
+        let count = shared.range(&storage, None, None, Order::Ascending).count();
+        assert_eq!(count, mock_keys().len());
+    }
+}
@@ -0,0 +1,261 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{
+    CheckedMultiplyFractionError, Decimal, Fraction, OverflowError, OverflowOperation, StdError,
+    Uint128,
+};
+
+use crate::{Asset, AssetError, AssetInfo, PriceSource};
+
+/// The direction in which a converted amount is rounded
+///
+/// As a rule of thumb, round down amounts that are paid out, and round up
+/// amounts that are charged, so that rounding never works against the contract.
+#[cw_serde]
+#[derive(Copy, Eq)]
+pub enum Rounding {
+    /// Round towards zero, e.g. for payouts
+    Floor,
+    /// Round away from zero, e.g. for charges
+    Ceil,
+}
+
+/// Multiply an amount by a fraction, rounding in the given direction
+pub(crate) fn mul_fraction<F: Fraction<Uint128>>(
+    amount: Uint128,
+    fraction: F,
+    rounding: Rounding,
+) -> Result<Uint128, AssetError> {
+    let result = match rounding {
+        Rounding::Floor => amount.checked_mul_floor(fraction),
+        Rounding::Ceil => amount.checked_mul_ceil(fraction),
+    };
+    result.map_err(|err| match err {
+        CheckedMultiplyFractionError::DivideByZero(err) => StdError::from(err).into(),
+        CheckedMultiplyFractionError::ConversionOverflow(_)
+        | CheckedMultiplyFractionError::Overflow(_) => {
+            OverflowError::new(OverflowOperation::Mul).into()
+        },
+    })
+}
+
+impl Asset {
+    /// Convert the **asset** into an equivalent amount of another asset, given
+    /// the exchange rate, i.e. the amount of the target asset that one unit of
+    /// this asset is worth
+    ///
+    /// Returns an `AssetError::Overflow` error if the converted amount does not
+    /// fit in `Uint128`.
+    ///
+    /// ```rust
+    /// use cosmwasm_std::Decimal;
+    /// use cw_asset::{Asset, AssetInfo, Rounding};
+    ///
+    /// let asset = Asset::native("uatom", 12345u128);
+    /// let rate = Decimal::percent(1050);
+    ///
+    /// let payout = asset.convert(AssetInfo::native("uusd"), rate, Rounding::Floor).unwrap();
+    /// assert_eq!(payout, Asset::native("uusd", 129622u128));
+    ///
+    /// let charge = asset.convert(AssetInfo::native("uusd"), rate, Rounding::Ceil).unwrap();
+    /// assert_eq!(charge, Asset::native("uusd", 129623u128));
+    /// ```
+    pub fn convert(
+        &self,
+        target: AssetInfo,
+        rate: Decimal,
+        rounding: Rounding,
+    ) -> Result<Asset, AssetError> {
+        Ok(Asset::new(target, mul_fraction(self.amount, rate, rounding)?))
+    }
+
+    /// Convert the **asset** into an equivalent amount of another asset, given
+    /// the exchange rate as a ratio of two amounts, e.g. the reserves of a pool
+    ///
+    /// `target_amount` of the target asset is worth `amount` of this asset.
+    /// Returns error if `amount` is zero, or an `AssetError::Overflow` error if
+    /// the converted amount does not fit in `Uint128`.
+    ///
+    /// ```rust
+    /// use cw_asset::{Asset, AssetInfo, Rounding};
+    ///
+    /// // 3 uatom is worth 1 uosmo
+    /// let asset = Asset::native("uatom", 100u128);
+    /// let converted =
+    ///     asset.convert_by_ratio(AssetInfo::native("uosmo"), 1u128, 3u128, Rounding::Floor).unwrap();
+    /// assert_eq!(converted, Asset::native("uosmo", 33u128));
+    /// ```
+    pub fn convert_by_ratio<A: Into<Uint128>, B: Into<Uint128>>(
+        &self,
+        target: AssetInfo,
+        target_amount: A,
+        amount: B,
+        rounding: Rounding,
+    ) -> Result<Asset, AssetError> {
+        let ratio = (target_amount.into(), amount.into());
+        Ok(Asset::new(target, mul_fraction(self.amount, ratio, rounding)?))
+    }
+
+    /// Convert the **asset** into an equivalent amount of another asset, given
+    /// the prices of both assets in a common quote asset
+    ///
+    /// The conversion is exact up to the final rounding. Returns error if the
+    /// target asset's price is zero, or an `AssetError::Overflow` error if the
+    /// converted amount does not fit in `Uint128`.
+    ///
+    /// ```rust
+    /// use cosmwasm_std::Decimal;
+    /// use cw_asset::{Asset, AssetInfo, Rounding};
+    ///
+    /// // 1 uatom is worth 10.5 uusd, and 1 uosmo is worth 0.7 uusd
+    /// let asset = Asset::native("uatom", 100u128);
+    /// let converted = asset
+    ///     .convert_by_prices(
+    ///         AssetInfo::native("uosmo"),
+    ///         Decimal::percent(1050),
+    ///         Decimal::percent(70),
+    ///         Rounding::Floor,
+    ///     )
+    ///     .unwrap();
+    /// assert_eq!(converted, Asset::native("uosmo", 1500u128));
+    /// ```
+    pub fn convert_by_prices(
+        &self,
+        target: AssetInfo,
+        price: Decimal,
+        target_price: Decimal,
+        rounding: Rounding,
+    ) -> Result<Asset, AssetError> {
+        // both prices have the same number of decimal places, so the ratio of
+        // their atomics is the exact exchange rate
+        self.convert_by_ratio(target, price.atomics(), target_price.atomics(), rounding)
+    }
+
+    /// Convert the **asset** into an equivalent amount of another asset, given
+    /// a **price source** providing the prices of both assets
+    ///
+    /// See [`Asset::convert_by_prices`] for details. Returns an
+    /// `AssetError::PriceNotFound` error if the price of either asset is
+    /// unknown.
+    pub fn convert_with_source(
+        &self,
+        target: AssetInfo,
+        source: &impl PriceSource,
+        rounding: Rounding,
+    ) -> Result<Asset, AssetError> {
+        let price = source.price(&self.info)?;
+        let target_price = source.price(&target)?;
+        self.convert_by_prices(target, price, target_price, rounding)
+    }
+}
+
+//------------------------------------------------------------------------------
+// Tests
+//------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{testing::mock_token, StaticPriceSource};
+
+    fn uusd() -> AssetInfo {
+        AssetInfo::native("uusd")
+    }
+
+    #[test]
+    fn converting_by_rate() {
+        let asset = Asset::native("uatom", 12345u128);
+        let rate = Decimal::percent(1050);
+
+        assert_eq!(
+            asset.convert(uusd(), rate, Rounding::Floor).unwrap(),
+            Asset::native("uusd", 129622u128),
+        );
+        assert_eq!(
+            asset.convert(uusd(), rate, Rounding::Ceil).unwrap(),
+            Asset::native("uusd", 129623u128),
+        );
+
+        // exact conversions are not rounded up
+        let rate = Decimal::percent(200);
+        assert_eq!(
+            asset.convert(uusd(), rate, Rounding::Ceil).unwrap(),
+            Asset::native("uusd", 24690u128),
+        );
+
+        let asset = Asset::native("uatom", u128::MAX);
+        let err = asset.convert(uusd(), rate, Rounding::Floor).unwrap_err();
+        assert_eq!(err, AssetError::Overflow(OverflowError::new(OverflowOperation::Mul)));
+    }
+
+    #[test]
+    fn converting_by_ratio() {
+        let asset = Asset::new(mock_token(), 100u128);
+
+        assert_eq!(
+            asset.convert_by_ratio(uusd(), 1u128, 3u128, Rounding::Floor).unwrap(),
+            Asset::native("uusd", 33u128),
+        );
+        assert_eq!(
+            asset.convert_by_ratio(uusd(), 1u128, 3u128, Rounding::Ceil).unwrap(),
+            Asset::native("uusd", 34u128),
+        );
+
+        // the intermediate product may exceed `Uint128`
+        let asset = Asset::new(mock_token(), u128::MAX);
+        assert_eq!(
+            asset.convert_by_ratio(uusd(), u128::MAX, u128::MAX, Rounding::Floor).unwrap(),
+            Asset::native("uusd", u128::MAX),
+        );
+
+        let err = asset.convert_by_ratio(uusd(), 2u128, 1u128, Rounding::Ceil).unwrap_err();
+        assert!(matches!(err, AssetError::Overflow(_)));
+
+        let err = asset.convert_by_ratio(uusd(), 1u128, 0u128, Rounding::Floor).unwrap_err();
+        assert!(matches!(err, AssetError::Std(StdError::DivideByZero { .. })));
+    }
+
+    #[test]
+    fn converting_by_prices() {
+        let asset = Asset::native("uatom", 100u128);
+
+        assert_eq!(
+            asset
+                .convert_by_prices(
+                    mock_token(),
+                    Decimal::percent(1050),
+                    Decimal::percent(70),
+                    Rounding::Floor
+                )
+                .unwrap(),
+            Asset::new(mock_token(), 1500u128),
+        );
+        assert_eq!(
+            asset
+                .convert_by_prices(
+                    mock_token(),
+                    Decimal::one(),
+                    Decimal::percent(300),
+                    Rounding::Ceil
+                )
+                .unwrap(),
+            Asset::new(mock_token(), 34u128),
+        );
+        assert!(asset
+            .convert_by_prices(mock_token(), Decimal::one(), Decimal::zero(), Rounding::Floor)
+            .is_err());
+
+        let prices = StaticPriceSource::new()
+            .with_price(AssetInfo::native("uatom"), Decimal::percent(1050))
+            .with_price(mock_token(), Decimal::percent(70));
+        assert_eq!(
+            asset.convert_with_source(mock_token(), &prices, Rounding::Floor).unwrap(),
+            Asset::new(mock_token(), 1500u128),
+        );
+        assert_eq!(
+            asset.convert_with_source(uusd(), &prices, Rounding::Floor),
+            Err(AssetError::PriceNotFound {
+                info: "native:uusd".into(),
+            }),
+        );
+    }
+}
@@ -1,6 +1,8 @@
-use cosmwasm_std::{OverflowError, StdError};
+use cosmwasm_std::{OverflowError, StdError, Uint128};
 use thiserror::Error;
 
+use crate::SlippageViolation;
+
 #[derive(Error, Debug, PartialEq)]
 pub enum AssetError {
     #[error("std error encountered while handling assets: {0}")]
@@ -58,4 +60,32 @@ pub enum AssetError {
     CannotCastToStdCoin {
         asset: String,
     },
+
+    #[error("invalid asset event `{ty}`: {reason}")]
+    InvalidAssetEvent {
+        ty: String,
+        reason: String,
+    },
+
+    #[error("received {received} of asset `{info}`, less than the minimum of {minimum}")]
+    ReceivedLessThanMinimum {
+        info: String,
+        minimum: Uint128,
+        received: Uint128,
+    },
+
+    #[error("price of asset `{info}` is not found")]
+    PriceNotFound {
+        info: String,
+    },
+
+    #[error("max slippage exceeded: {}", .violations.iter().map(ToString::to_string).collect::<Vec<_>>().join("; "))]
+    MaxSlippageExceeded {
+        violations: Vec<SlippageViolation>,
+    },
+
+    #[error("cannot send asset {asset} of zero amount")]
+    ZeroAmount {
+        asset: String,
+    },
 }
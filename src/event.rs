@@ -0,0 +1,469 @@
+use std::fmt;
+
+use cosmwasm_std::{Addr, Event};
+
+use crate::{Asset, AssetError, AssetInfo, AssetList};
+
+// keys of the attributes carried by asset events
+const ASSET_TYPE_KEY: &str = "asset_type";
+const ASSET_KEY: &str = "asset";
+const AMOUNT_KEY: &str = "amount";
+const FROM_KEY: &str = "from";
+const TO_KEY: &str = "to";
+
+// the attribute the chain adds to events emitted by contracts, carrying the
+// address of the emitting contract
+const CONTRACT_ADDRESS_KEY: &str = "_contract_address";
+
+// the chain prefixes types of events emitted by contracts with `wasm-`
+const WASM_EVENT_PREFIX: &str = "wasm-";
+
+/// The kind of movement of an asset that an [`AssetEvent`] records
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AssetEventKind {
+    /// The asset is transferred between two accounts
+    Transfer,
+    /// The asset is deposited into a contract
+    Deposit,
+    /// The asset is withdrawn from a contract
+    Withdraw,
+    /// The asset is minted to an account
+    Mint,
+    /// The asset is burned from an account
+    Burn,
+}
+
+impl AssetEventKind {
+    const ALL: [AssetEventKind; 5] = [
+        AssetEventKind::Transfer,
+        AssetEventKind::Deposit,
+        AssetEventKind::Withdraw,
+        AssetEventKind::Mint,
+        AssetEventKind::Burn,
+    ];
+
+    /// Return the type of events of this kind, e.g. `asset_transfer`
+    pub fn event_type(&self) -> &'static str {
+        match self {
+            AssetEventKind::Transfer => "asset_transfer",
+            AssetEventKind::Deposit => "asset_deposit",
+            AssetEventKind::Withdraw => "asset_withdraw",
+            AssetEventKind::Mint => "asset_mint",
+            AssetEventKind::Burn => "asset_burn",
+        }
+    }
+
+    /// Find the kind of an event given its type, with or without the `wasm-`
+    /// prefix added by the chain; `None` if it's not an asset event
+    pub fn from_event_type(ty: &str) -> Option<Self> {
+        let ty = ty.strip_prefix(WASM_EVENT_PREFIX).unwrap_or(ty);
+        Self::ALL.into_iter().find(|kind| kind.event_type() == ty)
+    }
+}
+
+impl fmt::Display for AssetEventKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.event_type())
+    }
+}
+
+/// A standardized record of an asset's movement, which can be converted to and
+/// from a `cosmwasm_std::Event` so that indexers can parse it
+///
+/// The event has the type given by [`AssetEventKind::event_type`], and carries
+/// the following attributes:
+///
+/// - `asset_type`: either `native` or `cw20`;
+/// - `asset`: the coin's denom or the token's contract address;
+/// - `amount`: the amount moved;
+/// - `from`: the account the asset is moved from; omitted for mints;
+/// - `to`: the account the asset is moved to; omitted for burns.
+///
+/// ```rust
+/// use cosmwasm_std::{Addr, Response};
+/// use cw_asset::{Asset, AssetError, AssetEvent};
+///
+/// fn withdraw(
+///     asset: &Asset,
+///     vault_addr: &Addr,
+///     user_addr: &Addr,
+/// ) -> Result<Response, AssetError> {
+///     Ok(Response::new()
+///         .add_message(asset.transfer_msg(user_addr)?)
+///         .add_event(AssetEvent::withdraw(asset.clone(), vault_addr, user_addr)))
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct AssetEvent {
+    pub kind: AssetEventKind,
+    pub asset: Asset,
+    pub from: Option<String>,
+    pub to: Option<String>,
+}
+
+impl AssetEvent {
+    /// Record an **asset** being transferred from one account to another
+    pub fn transfer<A: Into<String>, B: Into<String>>(asset: Asset, from: A, to: B) -> Self {
+        Self {
+            kind: AssetEventKind::Transfer,
+            asset,
+            from: Some(from.into()),
+            to: Some(to.into()),
+        }
+    }
+
+    /// Record an **asset** being deposited by an account into a contract
+    pub fn deposit<A: Into<String>, B: Into<String>>(asset: Asset, from: A, to: B) -> Self {
+        Self {
+            kind: AssetEventKind::Deposit,
+            asset,
+            from: Some(from.into()),
+            to: Some(to.into()),
+        }
+    }
+
+    /// Record an **asset** being withdrawn from a contract to an account
+    pub fn withdraw<A: Into<String>, B: Into<String>>(asset: Asset, from: A, to: B) -> Self {
+        Self {
+            kind: AssetEventKind::Withdraw,
+            asset,
+            from: Some(from.into()),
+            to: Some(to.into()),
+        }
+    }
+
+    /// Record an **asset** being minted to an account
+    pub fn mint<A: Into<String>>(asset: Asset, to: A) -> Self {
+        Self {
+            kind: AssetEventKind::Mint,
+            asset,
+            from: None,
+            to: Some(to.into()),
+        }
+    }
+
+    /// Record an **asset** being burned from an account
+    pub fn burn<A: Into<String>>(asset: Asset, from: A) -> Self {
+        Self {
+            kind: AssetEventKind::Burn,
+            asset,
+            from: Some(from.into()),
+            to: None,
+        }
+    }
+
+    /// Record every asset in an **asset list** moving between the same
+    /// accounts, one event per asset
+    ///
+    /// `from` and `to` are only included in the events if provided.
+    pub fn many(
+        kind: AssetEventKind,
+        list: &AssetList,
+        from: Option<&str>,
+        to: Option<&str>,
+    ) -> Vec<Event> {
+        list.into_iter()
+            .map(|asset| {
+                Self {
+                    kind,
+                    asset: asset.clone(),
+                    from: from.map(String::from),
+                    to: to.map(String::from),
+                }
+                .into()
+            })
+            .collect()
+    }
+
+    /// Parse an event into an **asset event**
+    ///
+    /// Returns `None` if the event is not an asset event, or error if it is,
+    /// but its attributes are malformed. CW20 contract addresses are not
+    /// validated, as the events are expected to come from the chain.
+    ///
+    /// NOTE: Any contract can emit events that look like asset events. Only
+    /// trust the parsed event if the message can only have executed known
+    /// contracts, or check its emitter using [`AssetEvent::emitter`].
+    pub fn parse(event: &Event) -> Result<Option<Self>, AssetError> {
+        let Some(kind) = AssetEventKind::from_event_type(&event.ty) else {
+            return Ok(None);
+        };
+
+        let find = |key: &str| {
+            event.attributes.iter().find(|attr| attr.key == key).map(|attr| attr.value.clone())
+        };
+        let require = |key: &str| {
+            find(key).ok_or_else(|| AssetError::InvalidAssetEvent {
+                ty: event.ty.clone(),
+                reason: format!("missing attribute `{key}`"),
+            })
+        };
+
+        let asset = require(ASSET_KEY)?;
+        let info = match require(ASSET_TYPE_KEY)?.as_str() {
+            "native" => AssetInfo::native(asset),
+            "cw20" => AssetInfo::cw20(Addr::unchecked(asset)),
+            ty => {
+                return Err(AssetError::InvalidAssetType {
+                    ty: ty.into(),
+                })
+            },
+        };
+
+        let amount_str = require(AMOUNT_KEY)?;
+        let amount = amount_str.parse::<u128>().map_err(|_| AssetError::InvalidAssetAmount {
+            amount: amount_str.clone(),
+        })?;
+
+        Ok(Some(Self {
+            kind,
+            asset: Asset::new(info, amount),
+            from: find(FROM_KEY),
+            to: find(TO_KEY),
+        }))
+    }
+
+    /// Return the address of the contract that emitted an event, as added by
+    /// the chain to the `wasm-` prefixed events; `None` if the event carries
+    /// no such address, e.g. because it's not emitted by a contract
+    pub fn emitter(event: &Event) -> Option<&str> {
+        event
+            .attributes
+            .iter()
+            .find(|attr| attr.key == CONTRACT_ADDRESS_KEY)
+            .map(|attr| attr.value.as_str())
+    }
+
+    /// Parse all asset events among a list of events, e.g. those of a
+    /// `Response` or a `SubMsgResponse`, skipping other events
+    ///
+    /// NOTE: See [`AssetEvent::parse`] regarding trusting the parsed events.
+    pub fn parse_all(events: &[Event]) -> Result<Vec<Self>, AssetError> {
+        events.iter().filter_map(|event| Self::parse(event).transpose()).collect()
+    }
+
+    /// Sum up the assets of all asset events of a specific kind among a list
+    /// of events into an **asset list**
+    ///
+    /// NOTE: Any contract can emit events that look like asset events. Only
+    /// trust the returned amounts if the message can only have executed known
+    /// contracts, or use [`AssetEvent::sum_from`] instead.
+    ///
+    /// ```rust
+    /// use cosmwasm_std::SubMsgResponse;
+    /// use cw_asset::{AssetError, AssetEvent, AssetEventKind, AssetList};
+    ///
+    /// fn withdrawn_assets(res: &SubMsgResponse) -> Result<AssetList, AssetError> {
+    ///     AssetEvent::sum(&res.events, AssetEventKind::Withdraw)
+    /// }
+    /// ```
+    pub fn sum(events: &[Event], kind: AssetEventKind) -> Result<AssetList, AssetError> {
+        let mut list = AssetList::new();
+        for event in Self::parse_all(events)? {
+            if event.kind == kind {
+                list.add(&event.asset)?;
+            }
+        }
+        Ok(list)
+    }
+
+    /// Sum up the assets of all asset events of a specific kind emitted by any
+    /// of the given contracts into an **asset list**
+    ///
+    /// Events are attributed to their emitter by the `_contract_address`
+    /// attribute added by the chain; events without it are skipped.
+    ///
+    /// ```rust
+    /// use cosmwasm_std::{Addr, SubMsgResponse};
+    /// use cw_asset::{AssetError, AssetEvent, AssetEventKind, AssetList};
+    ///
+    /// fn withdrawn_assets(res: &SubMsgResponse, vault: &Addr) -> Result<AssetList, AssetError> {
+    ///     AssetEvent::sum_from(&res.events, AssetEventKind::Withdraw, &[vault])
+    /// }
+    /// ```
+    pub fn sum_from(
+        events: &[Event],
+        kind: AssetEventKind,
+        emitters: &[&Addr],
+    ) -> Result<AssetList, AssetError> {
+        let trusted = events
+            .iter()
+            .filter(|event| {
+                Self::emitter(event)
+                    .is_some_and(|emitter| emitters.iter().any(|addr| addr.as_str() == emitter))
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+        Self::sum(&trusted, kind)
+    }
+}
+
+impl From<AssetEvent> for Event {
+    fn from(event: AssetEvent) -> Self {
+        let (asset_type, asset) = match &event.asset.info {
+            AssetInfo::Native(denom) => ("native", denom.clone()),
+            AssetInfo::Cw20(contract_addr) => ("cw20", contract_addr.to_string()),
+        };
+
+        let mut ev = Event::new(event.kind.event_type())
+            .add_attribute(ASSET_TYPE_KEY, asset_type)
+            .add_attribute(ASSET_KEY, asset)
+            .add_attribute(AMOUNT_KEY, event.asset.amount);
+        if let Some(from) = event.from {
+            ev = ev.add_attribute(FROM_KEY, from);
+        }
+        if let Some(to) = event.to {
+            ev = ev.add_attribute(TO_KEY, to);
+        }
+        ev
+    }
+}
+
+//------------------------------------------------------------------------------
+// Tests
+//------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::mock_token;
+
+    #[test]
+    fn building_events() {
+        let event: Event =
+            AssetEvent::transfer(Asset::native("uatom", 100u128), "alice", "bob").into();
+        assert_eq!(
+            event,
+            Event::new("asset_transfer")
+                .add_attribute("asset_type", "native")
+                .add_attribute("asset", "uatom")
+                .add_attribute("amount", "100")
+                .add_attribute("from", "alice")
+                .add_attribute("to", "bob"),
+        );
+
+        let event: Event = AssetEvent::mint(Asset::new(mock_token(), 200u128), "alice").into();
+        assert_eq!(
+            event,
+            Event::new("asset_mint")
+                .add_attribute("asset_type", "cw20")
+                .add_attribute("asset", "mock_token")
+                .add_attribute("amount", "200")
+                .add_attribute("to", "alice"),
+        );
+
+        let event: Event = AssetEvent::burn(Asset::new(mock_token(), 300u128), "bob").into();
+        assert_eq!(event.ty, "asset_burn");
+        assert_eq!(event.attributes.last().unwrap().key, "from");
+    }
+
+    #[test]
+    fn parsing_events() {
+        let assets = AssetList::from(vec![
+            Asset::native("uatom", 100u128),
+            Asset::new(mock_token(), 200u128),
+        ]);
+
+        for event in [
+            AssetEvent::transfer(assets[0].clone(), "alice", "bob"),
+            AssetEvent::deposit(assets[1].clone(), "alice", "vault"),
+            AssetEvent::withdraw(assets[0].clone(), "vault", "alice"),
+            AssetEvent::mint(assets[1].clone(), "alice"),
+            AssetEvent::burn(assets[0].clone(), "alice"),
+        ] {
+            let parsed = AssetEvent::parse(&event.clone().into()).unwrap();
+            assert_eq!(parsed, Some(event.clone()));
+
+            // as emitted by the chain
+            let mut wasm_event: Event = event.clone().into();
+            wasm_event.ty = format!("wasm-{}", wasm_event.ty);
+            wasm_event = wasm_event.add_attribute("_contract_address", "vault");
+            assert_eq!(AssetEvent::parse(&wasm_event).unwrap(), Some(event));
+        }
+
+        assert_eq!(AssetEvent::parse(&Event::new("transfer")).unwrap(), None);
+
+        let err = AssetEvent::parse(&Event::new("asset_mint").add_attribute("asset", "uatom"));
+        assert_eq!(
+            err,
+            Err(AssetError::InvalidAssetEvent {
+                ty: "asset_mint".into(),
+                reason: "missing attribute `asset_type`".into(),
+            }),
+        );
+
+        let err = AssetEvent::parse(
+            &Event::new("asset_mint")
+                .add_attribute("asset_type", "native")
+                .add_attribute("asset", "uatom")
+                .add_attribute("amount", "abc"),
+        );
+        assert_eq!(
+            err,
+            Err(AssetError::InvalidAssetAmount {
+                amount: "abc".into(),
+            }),
+        );
+    }
+
+    #[test]
+    fn summing_events() {
+        let assets = AssetList::from(vec![
+            Asset::native("uatom", 100u128),
+            Asset::new(mock_token(), 200u128),
+        ]);
+
+        let mut events = AssetEvent::many(AssetEventKind::Deposit, &assets, Some("alice"), None);
+        events.extend(AssetEvent::many(AssetEventKind::Deposit, &assets, Some("bob"), None));
+        events.push(Event::new("transfer").add_attribute("amount", "1uatom"));
+        events.push(AssetEvent::withdraw(assets[0].clone(), "vault", "alice").into());
+
+        assert_eq!(
+            AssetEvent::sum(&events, AssetEventKind::Deposit).unwrap(),
+            AssetList::from(vec![
+                Asset::native("uatom", 200u128),
+                Asset::new(mock_token(), 400u128),
+            ]),
+        );
+        assert_eq!(
+            AssetEvent::sum(&events, AssetEventKind::Withdraw).unwrap(),
+            AssetList::from(vec![Asset::native("uatom", 100u128)]),
+        );
+        assert_eq!(AssetEvent::sum(&events, AssetEventKind::Burn).unwrap(), AssetList::new());
+    }
+    #[test]
+    fn summing_events_from_emitters() {
+        let vault = Addr::unchecked("vault");
+        let attacker = Addr::unchecked("attacker");
+
+        let emit = |emitter: &Addr, amount: u128| {
+            let mut event: Event =
+                AssetEvent::withdraw(Asset::native("uatom", amount), emitter, "alice").into();
+            event.ty = format!("wasm-{}", event.ty);
+            event.add_attribute("_contract_address", emitter)
+        };
+        let events = vec![
+            emit(&vault, 100),
+            emit(&attacker, 1000),
+            // not emitted by a contract
+            AssetEvent::withdraw(Asset::native("uatom", 10u128), "vault", "alice").into(),
+            emit(&vault, 1),
+        ];
+
+        assert_eq!(AssetEvent::emitter(&events[0]), Some("vault"));
+        assert_eq!(AssetEvent::emitter(&events[2]), None);
+
+        assert_eq!(
+            AssetEvent::sum_from(&events, AssetEventKind::Withdraw, &[&vault]).unwrap(),
+            AssetList::from(vec![Asset::native("uatom", 101u128)]),
+        );
+        assert_eq!(
+            AssetEvent::sum_from(&events, AssetEventKind::Withdraw, &[&vault, &attacker]).unwrap(),
+            AssetList::from(vec![Asset::native("uatom", 1101u128)]),
+        );
+        assert_eq!(
+            AssetEvent::sum_from(&events, AssetEventKind::Withdraw, &[]).unwrap(),
+            AssetList::new(),
+        );
+    }
+}
@@ -0,0 +1,298 @@
+use std::{fmt, marker::PhantomData, ops::Deref, str::FromStr};
+
+use cosmwasm_schema::{
+    schemars::{
+        gen::SchemaGenerator,
+        schema::{Schema, SchemaObject, SubschemaValidation},
+        JsonSchema,
+    },
+    serde::{
+        de::{
+            self,
+            value::{MapAccessDeserializer, SeqAccessDeserializer},
+            MapAccess, SeqAccess, Visitor,
+        },
+        forward_to_deserialize_any, Deserialize, Deserializer, Serialize, Serializer,
+    },
+};
+
+use crate::{AssetError, AssetInfoUnchecked, AssetListUnchecked, AssetUnchecked};
+
+/// A wrapper around an unchecked asset type, which can be deserialized from
+/// either the type's canonical object form or its string forms, and is always
+/// serialized to the canonical object form
+///
+/// The accepted string forms are:
+///
+/// - [`AssetInfoUnchecked`]: `native:{denom}` or `cw20:{contract_addr}`;
+/// - [`AssetUnchecked`]: `native:{denom}:{amount}`, `cw20:{contract_addr}:{amount}`,
+///   or an SDK coin string such as `12345uatom`;
+/// - [`AssetListUnchecked`]: any of the asset string forms, separated by
+///   commas, e.g. `12345uatom,cw20:{contract_addr}:67890`.
+///
+/// This is useful for accepting assets from frontends that represent them
+/// inconsistently:
+///
+/// ```rust
+/// use cosmwasm_schema::cw_serde;
+/// use cosmwasm_std::from_json;
+/// use cw_asset::{AssetUnchecked, Flexible};
+///
+/// #[cw_serde]
+/// pub enum ExecuteMsg {
+///     Deposit {
+///         asset: Flexible<AssetUnchecked>,
+///     },
+/// }
+///
+/// let msg1: ExecuteMsg = from_json(r#"{"deposit":{"asset":"12345uatom"}}"#).unwrap();
+/// let msg2: ExecuteMsg = from_json(r#"{"deposit":{"asset":"native:uatom:12345"}}"#).unwrap();
+/// let msg3: ExecuteMsg =
+///     from_json(r#"{"deposit":{"asset":{"info":{"native":"uatom"},"amount":"12345"}}}"#).unwrap();
+///
+/// assert_eq!(msg1, msg2);
+/// assert_eq!(msg1, msg3);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Flexible<T>(pub T);
+
+impl<T> Flexible<T> {
+    /// Return the wrapped value
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> From<T> for Flexible<T> {
+    fn from(inner: T) -> Self {
+        Self(inner)
+    }
+}
+
+impl<T> Deref for Flexible<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+/// An unchecked asset type that can be parsed from a string
+pub trait FlexibleParse: Sized {
+    /// Parse the value from any of its string forms
+    fn parse_flexible(s: &str) -> Result<Self, AssetError>;
+}
+
+impl FlexibleParse for AssetInfoUnchecked {
+    fn parse_flexible(s: &str) -> Result<Self, AssetError> {
+        Self::from_str(s)
+    }
+}
+
+impl FlexibleParse for AssetUnchecked {
+    fn parse_flexible(s: &str) -> Result<Self, AssetError> {
+        // SDK coin strings never contain colons, while the other forms do
+        if s.contains(':') {
+            Self::from_str(s)
+        } else {
+            Self::from_sdk_string(s)
+        }
+    }
+}
+
+impl FlexibleParse for AssetListUnchecked {
+    fn parse_flexible(s: &str) -> Result<Self, AssetError> {
+        if s.is_empty() {
+            return Ok(Self::default());
+        }
+
+        let assets =
+            s.split(',').map(AssetUnchecked::parse_flexible).collect::<Result<Vec<_>, _>>()?;
+        Ok(assets.into())
+    }
+}
+
+impl<T: Serialize> Serialize for Flexible<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+/// A deserializer that deserializes newtype structs from their inner value, as
+/// JSON deserializers do, which `SeqAccessDeserializer` doesn't
+struct Transparent<D>(D);
+
+impl<'de, D: Deserializer<'de>> Deserializer<'de> for Transparent<D> {
+    type Error = D::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, D::Error> {
+        self.0.deserialize_any(visitor)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, D::Error> {
+        visitor.visit_newtype_struct(self.0)
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf
+        option unit unit_struct seq tuple tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+struct FlexibleVisitor<T>(PhantomData<T>);
+
+impl<'de, T> Visitor<'de> for FlexibleVisitor<T>
+where
+    T: Deserialize<'de> + FlexibleParse,
+{
+    type Value = Flexible<T>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("an asset in string or object form")
+    }
+
+    fn visit_str<E: de::Error>(self, s: &str) -> Result<Self::Value, E> {
+        T::parse_flexible(s).map(Flexible).map_err(E::custom)
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<Self::Value, A::Error> {
+        T::deserialize(MapAccessDeserializer::new(map)).map(Flexible)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, seq: A) -> Result<Self::Value, A::Error> {
+        T::deserialize(Transparent(SeqAccessDeserializer::new(seq))).map(Flexible)
+    }
+}
+
+impl<'de, T> Deserialize<'de> for Flexible<T>
+where
+    T: Deserialize<'de> + FlexibleParse,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(FlexibleVisitor(PhantomData))
+    }
+}
+
+impl<T: JsonSchema> JsonSchema for Flexible<T> {
+    fn schema_name() -> String {
+        format!("Flexible_for_{}", T::schema_name())
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        SchemaObject {
+            subschemas: Some(Box::new(SubschemaValidation {
+                any_of: Some(vec![gen.subschema_for::<String>(), gen.subschema_for::<T>()]),
+                ..Default::default()
+            })),
+            ..Default::default()
+        }
+        .into()
+    }
+}
+
+//------------------------------------------------------------------------------
+// Tests
+//------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::{from_json, to_json_string, StdError};
+
+    use super::*;
+
+    #[test]
+    fn deserializing_asset_info() {
+        let expected = Flexible(AssetInfoUnchecked::native("uatom"));
+        assert_eq!(
+            from_json::<Flexible<AssetInfoUnchecked>>(r#""native:uatom""#).unwrap(),
+            expected,
+        );
+        assert_eq!(
+            from_json::<Flexible<AssetInfoUnchecked>>(r#"{"native":"uatom"}"#).unwrap(),
+            expected,
+        );
+
+        let expected = Flexible(AssetInfoUnchecked::cw20("mock_token"));
+        assert_eq!(
+            from_json::<Flexible<AssetInfoUnchecked>>(r#""cw20:mock_token""#).unwrap(),
+            expected,
+        );
+
+        let err = from_json::<Flexible<AssetInfoUnchecked>>(r#""uatom""#).unwrap_err();
+        assert!(matches!(err, StdError::ParseErr { .. }));
+    }
+
+    #[test]
+    fn deserializing_asset() {
+        let expected = Flexible(AssetUnchecked::native("uatom", 100u128));
+        for json in [
+            r#""native:uatom:100""#,
+            r#""100uatom""#,
+            r#"{"info":{"native":"uatom"},"amount":"100"}"#,
+        ] {
+            assert_eq!(from_json::<Flexible<AssetUnchecked>>(json).unwrap(), expected);
+        }
+
+        let expected = Flexible(AssetUnchecked::cw20("mock_token", 100u128));
+        assert_eq!(
+            from_json::<Flexible<AssetUnchecked>>(r#""cw20:mock_token:100""#).unwrap(),
+            expected,
+        );
+
+        for json in [r#""uatom""#, r#""native:uatom""#, r#"{"info":{"native":"uatom"}}"#, "100"] {
+            assert!(from_json::<Flexible<AssetUnchecked>>(json).is_err());
+        }
+
+        // errors in the object form are reported as is
+        let json = r#"{"info":{"native":"uatom"},"amount":"abc"}"#;
+        let err = from_json::<Flexible<AssetUnchecked>>(json).unwrap_err();
+        assert!(err.to_string().ends_with("Parsing u128: invalid digit found in string"), "{err}");
+
+        let err = from_json::<Flexible<AssetUnchecked>>(r#""native:uatom:abc""#).unwrap_err();
+        assert!(err.to_string().contains("invalid asset amount"), "{err}");
+    }
+
+    #[test]
+    fn deserializing_asset_list() {
+        let expected = Flexible(AssetListUnchecked::from(vec![
+            AssetUnchecked::native("uatom", 100u128),
+            AssetUnchecked::cw20("mock_token", 200u128),
+        ]));
+        for json in [
+            r#""100uatom,cw20:mock_token:200""#,
+            r#""native:uatom:100,cw20:mock_token:200""#,
+            r#"[{"info":{"native":"uatom"},"amount":"100"},{"info":{"cw20":"mock_token"},"amount":"200"}]"#,
+        ] {
+            assert_eq!(from_json::<Flexible<AssetListUnchecked>>(json).unwrap(), expected);
+        }
+
+        assert_eq!(
+            from_json::<Flexible<AssetListUnchecked>>(r#""""#).unwrap(),
+            Flexible(AssetListUnchecked::default()),
+        );
+        assert!(from_json::<Flexible<AssetListUnchecked>>(r#""100uatom,""#).is_err());
+
+        let json = r#"[{"info":{"native":"uatom"},"amount":"abc"}]"#;
+        let err = from_json::<Flexible<AssetListUnchecked>>(json).unwrap_err();
+        assert!(err.to_string().ends_with("Parsing u128: invalid digit found in string"), "{err}");
+    }
+
+    #[test]
+    fn serializing() {
+        let asset = Flexible(AssetUnchecked::native("uatom", 100u128));
+        assert_eq!(
+            to_json_string(&asset).unwrap(),
+            r#"{"info":{"native":"uatom"},"amount":"100"}"#,
+        );
+
+        let list: Flexible<AssetListUnchecked> = from_json(r#""100uatom""#).unwrap();
+        assert_eq!(
+            to_json_string(&list).unwrap(),
+            r#"[{"info":{"native":"uatom"},"amount":"100"}]"#,
+        );
+    }
+}
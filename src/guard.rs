@@ -0,0 +1,262 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, QuerierWrapper, StdResult, Storage};
+use cw_storage_plus::Item;
+
+use crate::{Asset, AssetError, AssetInfo, AssetList};
+
+/// An address' balances of a set of assets at a point in time
+#[cw_serde]
+pub struct BalanceSnapshot {
+    /// The address whose balances are recorded
+    pub address: Addr,
+    /// The recorded balances, including zero ones
+    pub balances: Vec<Asset>,
+}
+
+/// A storage primitive that measures the assets an address receives across
+/// `SubMsg`s, by comparing its balances before and after
+///
+/// Before dispatching a `SubMsg`, take a snapshot of the balances of the assets
+/// of interest using [`BalanceGuard::snapshot`]. Then, in the reply, compute
+/// the amounts received using [`BalanceGuard::received`], optionally asserting
+/// a minimum amount of each asset using [`BalanceGuard::received_at_least`],
+/// e.g. for slippage protection. Both consume the snapshot.
+///
+/// Balances are queried using [`AssetInfo::query_balance`].
+///
+/// ```rust
+/// use cosmwasm_std::{Addr, CosmosMsg, DepsMut, Env, Reply, Response, SubMsg};
+/// use cw_asset::{AssetError, AssetInfo, AssetList, BalanceGuard};
+///
+/// const GUARD: BalanceGuard = BalanceGuard::new("swap_guard");
+///
+/// fn swap(
+///     deps: DepsMut,
+///     env: Env,
+///     ask: AssetInfo,
+///     swap_msg: CosmosMsg,
+/// ) -> Result<Response, AssetError> {
+///     GUARD.snapshot(deps.storage, &deps.querier, &env.contract.address, &[ask])?;
+///     Ok(Response::new().add_submessage(SubMsg::reply_on_success(swap_msg, 1)))
+/// }
+///
+/// fn after_swap(deps: DepsMut, minimum: AssetList) -> Result<Response, AssetError> {
+///     let received = GUARD.received_at_least(deps.storage, &deps.querier, &minimum)?;
+///     Ok(Response::new().add_attribute("received", received.to_string()))
+/// }
+/// ```
+pub struct BalanceGuard {
+    namespace: &'static str,
+}
+
+impl BalanceGuard {
+    /// Create a new **balance guard** instance, given the namespace under which
+    /// the snapshot is stored
+    pub const fn new(namespace: &'static str) -> Self {
+        Self {
+            namespace,
+        }
+    }
+
+    fn item(&self) -> Item<BalanceSnapshot> {
+        Item::new(self.namespace)
+    }
+
+    /// Return the stored snapshot, if any
+    pub fn load(&self, storage: &dyn Storage) -> StdResult<Option<BalanceSnapshot>> {
+        self.item().may_load(storage)
+    }
+
+    /// Query an address' balances of the given assets, and store them as the
+    /// snapshot, replacing any existing one
+    ///
+    /// Duplicate assets in `infos` are only snapshotted once.
+    pub fn snapshot(
+        &self,
+        storage: &mut dyn Storage,
+        querier: &QuerierWrapper,
+        address: &Addr,
+        infos: &[AssetInfo],
+    ) -> Result<(), AssetError> {
+        let mut balances: Vec<Asset> = vec![];
+        for info in infos {
+            // each asset is only snapshotted once, so that it's not counted
+            // more than once when computing the amounts received
+            if balances.iter().any(|balance| balance.info == *info) {
+                continue;
+            }
+            balances.push(Asset::new(info.clone(), info.query_balance(querier, address)?));
+        }
+
+        self.item().save(
+            storage,
+            &BalanceSnapshot {
+                address: address.clone(),
+                balances,
+            },
+        )?;
+
+        Ok(())
+    }
+
+    /// Compute the amounts of the snapshotted assets that the address has
+    /// received since the snapshot was taken, and remove the snapshot
+    ///
+    /// Assets whose balances haven't increased are omitted. Returns error if
+    /// no snapshot is stored.
+    pub fn received(
+        &self,
+        storage: &mut dyn Storage,
+        querier: &QuerierWrapper,
+    ) -> Result<AssetList, AssetError> {
+        let snapshot = self.item().load(storage)?;
+        self.item().remove(storage);
+
+        let mut received = AssetList::new();
+        for before in &snapshot.balances {
+            let after = before.info.query_balance(querier, &snapshot.address)?;
+            received.add(&Asset::new(before.info.clone(), after.saturating_sub(before.amount)))?;
+        }
+
+        Ok(received)
+    }
+
+    /// Same as [`BalanceGuard::received`], but additionally assert that the
+    /// address has received at least the amount of each **asset** in `minimum`
+    ///
+    /// Returns an `AssetError::ReceivedLessThanMinimum` error if it has not.
+    pub fn received_at_least(
+        &self,
+        storage: &mut dyn Storage,
+        querier: &QuerierWrapper,
+        minimum: &AssetList,
+    ) -> Result<AssetList, AssetError> {
+        let received = self.received(storage, querier)?;
+
+        for min in minimum {
+            let amount = received.find(&min.info).map(|asset| asset.amount).unwrap_or_default();
+            if amount < min.amount {
+                return Err(AssetError::ReceivedLessThanMinimum {
+                    info: min.info.to_string(),
+                    minimum: min.amount,
+                    received: amount,
+                });
+            }
+        }
+
+        Ok(received)
+    }
+}
+
+//------------------------------------------------------------------------------
+// Tests
+//------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::{testing::MockStorage, Coin, StdError, Uint128};
+
+    use super::*;
+    use crate::testing::{mock_token, CustomMockQuerier};
+
+    const GUARD: BalanceGuard = BalanceGuard::new("guard");
+
+    fn infos() -> Vec<AssetInfo> {
+        vec![AssetInfo::native("uatom"), AssetInfo::native("uosmo"), mock_token()]
+    }
+
+    fn mock_querier() -> CustomMockQuerier {
+        CustomMockQuerier::default()
+            .with_base_balances("contract", &[Coin::new(100u128, "uatom")])
+            .with_cw20_balance("mock_token", "contract", 200)
+    }
+
+    #[test]
+    fn measuring_received() {
+        let mut storage = MockStorage::default();
+        let mut querier = mock_querier();
+        let contract = Addr::unchecked("contract");
+
+        GUARD.snapshot(&mut storage, &QuerierWrapper::new(&querier), &contract, &infos()).unwrap();
+        assert_eq!(
+            GUARD.load(&storage).unwrap().unwrap().balances,
+            vec![
+                Asset::native("uatom", 100u128),
+                Asset::native("uosmo", 0u128),
+                Asset::new(mock_token(), 200u128),
+            ],
+        );
+
+        // the contract spends some uatom, and receives uosmo and the cw20 token
+        querier.set_base_balances(
+            "contract",
+            &[Coin::new(50u128, "uatom"), Coin::new(300u128, "uosmo")],
+        );
+        querier.set_cw20_balance("mock_token", "contract", 250);
+
+        let received = GUARD.received(&mut storage, &QuerierWrapper::new(&querier)).unwrap();
+        assert_eq!(
+            received,
+            AssetList::from(vec![
+                Asset::native("uosmo", 300u128),
+                Asset::new(mock_token(), 50u128)
+            ]),
+        );
+
+        // the snapshot is consumed
+        assert_eq!(GUARD.load(&storage).unwrap(), None);
+        let err = GUARD.received(&mut storage, &QuerierWrapper::new(&querier)).unwrap_err();
+        assert!(matches!(err, AssetError::Std(StdError::NotFound { .. })));
+    }
+
+    #[test]
+    fn asserting_minimum() {
+        let mut storage = MockStorage::default();
+        let mut querier = mock_querier();
+        let contract = Addr::unchecked("contract");
+
+        querier.set_cw20_balance("mock_token", "contract", 200);
+        GUARD.snapshot(&mut storage, &QuerierWrapper::new(&querier), &contract, &infos()).unwrap();
+        querier.set_cw20_balance("mock_token", "contract", 250);
+
+        let minimum = AssetList::from(vec![Asset::new(mock_token(), 50u128)]);
+        let received = GUARD
+            .received_at_least(&mut storage, &QuerierWrapper::new(&querier), &minimum)
+            .unwrap();
+        assert_eq!(received, minimum);
+
+        GUARD.snapshot(&mut storage, &QuerierWrapper::new(&querier), &contract, &infos()).unwrap();
+        querier.set_cw20_balance("mock_token", "contract", 299);
+
+        let minimum =
+            AssetList::from(vec![Asset::new(mock_token(), 50u128), Asset::native("uosmo", 1u128)]);
+        let err = GUARD.received_at_least(&mut storage, &QuerierWrapper::new(&querier), &minimum);
+        assert_eq!(
+            err,
+            Err(AssetError::ReceivedLessThanMinimum {
+                info: "cw20:mock_token".into(),
+                minimum: Uint128::new(50),
+                received: Uint128::new(49),
+            }),
+        );
+    }
+
+    #[test]
+    fn snapshotting_duplicates_once() {
+        let mut storage = MockStorage::default();
+        let mut querier = mock_querier();
+        let contract = Addr::unchecked("contract");
+
+        let infos = vec![mock_token(), AssetInfo::native("uatom"), mock_token()];
+        GUARD.snapshot(&mut storage, &QuerierWrapper::new(&querier), &contract, &infos).unwrap();
+        assert_eq!(
+            GUARD.load(&storage).unwrap().unwrap().balances,
+            vec![Asset::new(mock_token(), 200u128), Asset::native("uatom", 100u128)],
+        );
+
+        querier.set_cw20_balance("mock_token", "contract", 250);
+
+        let received = GUARD.received(&mut storage, &QuerierWrapper::new(&querier)).unwrap();
+        assert_eq!(received, AssetList::from(vec![Asset::new(mock_token(), 50u128)]));
+    }
+}
@@ -0,0 +1,317 @@
+use cosmwasm_schema::{
+    cw_serde,
+    serde::{de::DeserializeOwned, Serialize},
+};
+use cosmwasm_std::{from_json, to_json_binary, Addr, Api, CosmosMsg, MessageInfo};
+use cw20::Cw20ReceiveMsg;
+
+use crate::{Asset, AssetError, AssetInfo, AssetList};
+
+/// An execute message for receiving assets along with a hook message, through
+/// either of the two paths by which assets can be sent to a contract
+///
+/// - CW20 tokens are sent via the token contract's `Send` method, which invokes
+///   the `Receive` variant with the hook message encoded in binary;
+/// - native coins are attached to a `ReceiveNative` message, which contains the
+///   same hook message as is.
+///
+/// Messages depositing assets into such a contract are generated by
+/// [`Asset::deposit_hook_msg`] and [`AssetList::deposit_hook_msgs`].
+///
+/// ```rust
+/// use cosmwasm_schema::cw_serde;
+/// use cosmwasm_std::{DepsMut, MessageInfo, Response};
+/// use cw_asset::{AssetError, ReceiveMsg, Received};
+///
+/// #[cw_serde]
+/// enum HookMsg {
+///     Deposit {},
+/// }
+///
+/// fn execute(
+///     deps: DepsMut,
+///     info: MessageInfo,
+///     msg: ReceiveMsg<HookMsg>,
+/// ) -> Result<Response, AssetError> {
+///     let Received {
+///         sender,
+///         assets,
+///         msg,
+///     } = msg.dispatch(deps.api, &info)?;
+///
+///     match msg {
+///         HookMsg::Deposit {} => Ok(Response::new()
+///             .add_attribute("depositor", sender)
+///             .add_attribute("assets", assets.to_string())),
+///     }
+/// }
+/// ```
+#[cw_serde]
+pub enum ReceiveMsg<M> {
+    /// Receive CW20 tokens, invoked by the token contract
+    Receive(Cw20ReceiveMsg),
+    /// Receive the native coins attached to the message
+    ReceiveNative(M),
+}
+
+/// The assets received by a contract and the hook message that came with them,
+/// as returned by [`ReceiveMsg::dispatch`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Received<M> {
+    /// The address that sent the assets, i.e. the sender of the CW20 tokens, or
+    /// the sender of the message that the native coins were attached to
+    pub sender: Addr,
+    /// The assets received
+    pub assets: AssetList,
+    /// The decoded hook message
+    pub msg: M,
+}
+
+impl<M: DeserializeOwned> ReceiveMsg<M> {
+    /// Return the assets received, their sender, and the decoded hook message
+    ///
+    /// For the `Receive` variant, the message is assumed to be invoked by the
+    /// CW20 token contract, so the asset is the token whose address is the
+    /// message's sender. Check it against a whitelist if only certain tokens
+    /// should be accepted. Coins attached to the message are ignored.
+    ///
+    /// For the `ReceiveNative` variant, the assets are the coins attached to
+    /// the message, excluding those of zero amount.
+    pub fn dispatch(self, api: &dyn Api, info: &MessageInfo) -> Result<Received<M>, AssetError> {
+        match self {
+            ReceiveMsg::Receive(cw20_msg) => Ok(Received {
+                sender: api.addr_validate(&cw20_msg.sender)?,
+                assets: AssetList::from(vec![Asset::cw20(info.sender.clone(), cw20_msg.amount)]),
+                msg: from_json(&cw20_msg.msg)?,
+            }),
+            ReceiveMsg::ReceiveNative(msg) => Ok(Received {
+                sender: info.sender.clone(),
+                assets: AssetList::from(&info.funds).purge().clone(),
+                msg,
+            }),
+        }
+    }
+}
+
+impl Asset {
+    /// Generate a message that deposits the **asset** into a contract that
+    /// dispatches the assets it receives using [`ReceiveMsg`], along with a
+    /// hook message
+    ///
+    /// - For CW20 tokens, this is a CW20 `Send` message carrying the hook
+    ///   message, which invokes the `Receive` variant.
+    /// - For native coins, this executes the contract with the `ReceiveNative`
+    ///   variant wrapping the hook message, and the coin attached as funds.
+    ///
+    /// ```rust
+    /// use cosmwasm_schema::cw_serde;
+    /// use cosmwasm_std::{Addr, Response};
+    /// use cw_asset::{Asset, AssetError};
+    ///
+    /// #[cw_serde]
+    /// enum HookMsg {
+    ///     Deposit {},
+    /// }
+    ///
+    /// fn forward_asset(asset: &Asset, vault_addr: &Addr) -> Result<Response, AssetError> {
+    ///     let msg = asset.deposit_hook_msg(vault_addr, &HookMsg::Deposit {})?;
+    ///
+    ///     Ok(Response::new().add_message(msg).add_attribute("asset_deposited", asset.to_string()))
+    /// }
+    /// ```
+    pub fn deposit_hook_msg<A: Into<String>, M: Serialize>(
+        &self,
+        contract: A,
+        msg: &M,
+    ) -> Result<CosmosMsg, AssetError> {
+        match &self.info {
+            AssetInfo::Cw20(_) => self.deposit_msg(contract, to_json_binary(msg)?),
+            AssetInfo::Native(_) => {
+                self.deposit_msg(contract, to_json_binary(&ReceiveMsg::ReceiveNative(msg))?)
+            },
+        }
+    }
+}
+
+impl AssetList {
+    /// Generate a message for every **asset** in the list that deposits it into
+    /// a contract that dispatches the assets it receives using [`ReceiveMsg`],
+    /// along with a hook message
+    ///
+    /// See [`Asset::deposit_hook_msg`] for details.
+    pub fn deposit_hook_msgs<A: Into<String> + Clone, M: Serialize>(
+        &self,
+        contract: A,
+        msg: &M,
+    ) -> Result<Vec<CosmosMsg>, AssetError> {
+        self.into_iter().map(|asset| asset.deposit_hook_msg(contract.clone(), msg)).collect()
+    }
+}
+
+//------------------------------------------------------------------------------
+// Tests
+//------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::{
+        coin, testing::MockApi, to_json_string, Binary, Coin, StdError, Uint128, WasmMsg,
+    };
+    use cw20::Cw20ExecuteMsg;
+
+    use super::*;
+
+    #[cw_serde]
+    enum HookMsg {
+        Deposit {
+            recipient: Option<String>,
+        },
+    }
+
+    fn mock_info(sender: &Addr, funds: &[Coin]) -> MessageInfo {
+        MessageInfo {
+            sender: sender.clone(),
+            funds: funds.to_vec(),
+        }
+    }
+
+    fn hook() -> HookMsg {
+        HookMsg::Deposit {
+            recipient: None,
+        }
+    }
+
+    #[test]
+    fn serializing() {
+        let msg = ReceiveMsg::ReceiveNative(hook());
+        assert_eq!(
+            to_json_string(&msg).unwrap(),
+            r#"{"receive_native":{"deposit":{"recipient":null}}}"#,
+        );
+
+        let msg = ReceiveMsg::<HookMsg>::Receive(Cw20ReceiveMsg {
+            sender: "alice".into(),
+            amount: Uint128::new(12345),
+            msg: Binary::default(),
+        });
+        assert_eq!(
+            to_json_string(&msg).unwrap(),
+            r#"{"receive":{"sender":"alice","amount":"12345","msg":""}}"#,
+        );
+    }
+
+    #[test]
+    fn dispatching_cw20() {
+        let api = MockApi::default();
+        let token = api.addr_make("token");
+        let alice = api.addr_make("alice");
+
+        let msg = ReceiveMsg::<HookMsg>::Receive(Cw20ReceiveMsg {
+            sender: alice.to_string(),
+            amount: Uint128::new(12345),
+            msg: to_json_binary(&hook()).unwrap(),
+        });
+        assert_eq!(
+            msg.dispatch(&api, &mock_info(&token, &[])).unwrap(),
+            Received {
+                sender: alice.clone(),
+                assets: AssetList::from(vec![Asset::cw20(token.clone(), 12345u128)]),
+                msg: hook(),
+            },
+        );
+
+        let msg = ReceiveMsg::<HookMsg>::Receive(Cw20ReceiveMsg {
+            sender: alice.to_string(),
+            amount: Uint128::new(12345),
+            msg: to_json_binary(&"withdraw").unwrap(),
+        });
+        let err = msg.dispatch(&api, &mock_info(&token, &[])).unwrap_err();
+        assert!(matches!(err, AssetError::Std(StdError::ParseErr { .. })));
+
+        let msg = ReceiveMsg::<HookMsg>::Receive(Cw20ReceiveMsg {
+            sender: "alice".into(),
+            amount: Uint128::new(12345),
+            msg: to_json_binary(&hook()).unwrap(),
+        });
+        assert!(msg.dispatch(&api, &mock_info(&token, &[])).is_err());
+    }
+
+    #[test]
+    fn dispatching_native() {
+        let api = MockApi::default();
+        let alice = api.addr_make("alice");
+
+        let msg = ReceiveMsg::ReceiveNative(hook());
+        let info = mock_info(&alice, &[coin(12345, "uatom"), coin(0, "uosmo"), coin(1, "uusd")]);
+        assert_eq!(
+            msg.dispatch(&api, &info).unwrap(),
+            Received {
+                sender: alice,
+                assets: AssetList::from(vec![
+                    Asset::native("uatom", 12345u128),
+                    Asset::native("uusd", 1u128),
+                ]),
+                msg: hook(),
+            },
+        );
+    }
+
+    /// Deliver a message generated by `deposit_hook_msg` to the receiving
+    /// contract, as the chain and the CW20 token contract would
+    fn deliver(api: &MockApi, router: &Addr, msg: CosmosMsg) -> Received<HookMsg> {
+        let CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr,
+            msg,
+            funds,
+        }) = msg
+        else {
+            panic!("expected a wasm execute message");
+        };
+
+        match from_json::<Cw20ExecuteMsg>(&msg) {
+            Ok(Cw20ExecuteMsg::Send {
+                contract: _,
+                amount,
+                msg,
+            }) => {
+                let receive_msg = ReceiveMsg::<HookMsg>::Receive(Cw20ReceiveMsg {
+                    sender: router.to_string(),
+                    amount,
+                    msg,
+                });
+                let token = Addr::unchecked(contract_addr);
+                receive_msg.dispatch(api, &mock_info(&token, &[])).unwrap()
+            },
+            _ => {
+                let receive_msg: ReceiveMsg<HookMsg> = from_json(&msg).unwrap();
+                receive_msg.dispatch(api, &mock_info(router, &funds)).unwrap()
+            },
+        }
+    }
+
+    #[test]
+    fn depositing_into_receivers() {
+        let api = MockApi::default();
+        let router = api.addr_make("router");
+        let vault = api.addr_make("vault");
+        let token = api.addr_make("token");
+
+        let list = AssetList::from(vec![
+            Asset::native("uatom", 12345u128),
+            Asset::cw20(token.clone(), 67890u128),
+        ]);
+        let msgs = list.deposit_hook_msgs(&vault, &hook()).unwrap();
+        assert_eq!(msgs.len(), 2);
+
+        for (asset, msg) in list.into_iter().zip(msgs) {
+            assert_eq!(
+                deliver(&api, &router, msg),
+                Received {
+                    sender: router.clone(),
+                    assets: AssetList::from(vec![asset.clone()]),
+                    msg: hook(),
+                },
+            );
+        }
+    }
+}
@@ -0,0 +1,252 @@
+use cosmwasm_schema::serde::{de::DeserializeOwned, Serialize};
+use cw_storage_plus::MultiIndex;
+
+use crate::{Asset, AssetInfo, CompactAssetInfo};
+
+/// Types that contain an **asset info**, and hence can be indexed by it in an
+/// `IndexedMap` using [`asset_info_index`]
+pub trait HasAssetInfo {
+    /// Return a reference to the asset info contained in the value
+    fn asset_info(&self) -> &AssetInfo;
+}
+
+impl HasAssetInfo for AssetInfo {
+    fn asset_info(&self) -> &AssetInfo {
+        self
+    }
+}
+
+impl HasAssetInfo for Asset {
+    fn asset_info(&self) -> &AssetInfo {
+        &self.info
+    }
+}
+
+fn index_by_asset_info<T: HasAssetInfo>(_pk: &[u8], value: &T) -> CompactAssetInfo {
+    value.asset_info().into()
+}
+
+/// Create a `MultiIndex` that indexes values of an `IndexedMap` by the asset
+/// info they contain
+///
+/// The index key uses the [`CompactAssetInfo`] encoding, so in addition to
+/// querying all entries of a specific asset with `prefix`, the entries of all
+/// native coins or all CW20 tokens can be iterated over with `sub_prefix`, by
+/// providing [`NATIVE_TAG`](crate::NATIVE_TAG) or [`CW20_TAG`](crate::CW20_TAG).
+///
+/// ```rust
+/// use cosmwasm_schema::cw_serde;
+/// use cosmwasm_std::{Addr, Order, StdResult, Storage};
+/// use cw_asset::{
+///     asset_info_index, Asset, AssetInfo, CompactAssetInfo, HasAssetInfo, NATIVE_TAG,
+/// };
+/// use cw_storage_plus::{Index, IndexList, IndexedMap, MultiIndex};
+///
+/// #[cw_serde]
+/// struct Position {
+///     owner: Addr,
+///     collateral: Asset,
+/// }
+///
+/// impl HasAssetInfo for Position {
+///     fn asset_info(&self) -> &AssetInfo {
+///         &self.collateral.info
+///     }
+/// }
+///
+/// struct PositionIndexes<'a> {
+///     collateral: MultiIndex<'a, CompactAssetInfo, Position, u64>,
+/// }
+///
+/// impl<'a> IndexList<Position> for PositionIndexes<'a> {
+///     fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Position>> + '_> {
+///         let v: Vec<&dyn Index<Position>> = vec![&self.collateral];
+///         Box::new(v.into_iter())
+///     }
+/// }
+///
+/// const POSITIONS: IndexedMap<u64, Position, PositionIndexes> = IndexedMap::new(
+///     "positions",
+///     PositionIndexes {
+///         collateral: asset_info_index("positions", "positions__collateral"),
+///     },
+/// );
+///
+/// fn native_positions(storage: &dyn Storage) -> StdResult<Vec<(u64, Position)>> {
+///     POSITIONS
+///         .idx
+///         .collateral
+///         .sub_prefix(NATIVE_TAG)
+///         .range(storage, None, None, Order::Ascending)
+///         .collect()
+/// }
+///
+/// fn positions_by_collateral(
+///     storage: &dyn Storage,
+///     info: &AssetInfo,
+/// ) -> StdResult<Vec<(u64, Position)>> {
+///     POSITIONS
+///         .idx
+///         .collateral
+///         .prefix(info.into())
+///         .range(storage, None, None, Order::Ascending)
+///         .collect()
+/// }
+/// ```
+pub const fn asset_info_index<'a, T, PK>(
+    pk_namespace: &'a str,
+    idx_namespace: &'static str,
+) -> MultiIndex<'a, CompactAssetInfo, T, PK>
+where
+    T: HasAssetInfo + Serialize + DeserializeOwned + Clone,
+{
+    MultiIndex::new(index_by_asset_info::<T>, pk_namespace, idx_namespace)
+}
+
+//------------------------------------------------------------------------------
+// Tests
+//------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_schema::cw_serde;
+    use cosmwasm_std::{testing::MockStorage, Addr, Order, StdResult};
+    use cw_storage_plus::{Index, IndexList, IndexedMap};
+
+    use super::*;
+    use crate::{CW20_TAG, NATIVE_TAG};
+
+    #[cw_serde]
+    struct MockOrder {
+        owner: String,
+        offer: Asset,
+    }
+
+    impl HasAssetInfo for MockOrder {
+        fn asset_info(&self) -> &AssetInfo {
+            &self.offer.info
+        }
+    }
+
+    struct MockOrderIndexes<'a> {
+        offer: MultiIndex<'a, CompactAssetInfo, MockOrder, u64>,
+    }
+
+    impl<'a> IndexList<MockOrder> for MockOrderIndexes<'a> {
+        fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<MockOrder>> + '_> {
+            let v: Vec<&dyn Index<MockOrder>> = vec![&self.offer];
+            Box::new(v.into_iter())
+        }
+    }
+
+    fn orders<'a>() -> IndexedMap<u64, MockOrder, MockOrderIndexes<'a>> {
+        IndexedMap::new(
+            "orders",
+            MockOrderIndexes {
+                offer: asset_info_index("orders", "orders__offer"),
+            },
+        )
+    }
+
+    fn mock_orders() -> Vec<MockOrder> {
+        vec![
+            MockOrder {
+                owner: "alice".into(),
+                offer: Asset::native("uatom", 100u128),
+            },
+            MockOrder {
+                owner: "bob".into(),
+                offer: Asset::cw20(Addr::unchecked("mock_token"), 200u128),
+            },
+            MockOrder {
+                owner: "charlie".into(),
+                offer: Asset::native("uosmo", 300u128),
+            },
+            MockOrder {
+                owner: "dave".into(),
+                offer: Asset::native("uatom", 400u128),
+            },
+        ]
+    }
+
+    fn setup() -> MockStorage {
+        let mut storage = MockStorage::default();
+        for (id, order) in mock_orders().iter().enumerate() {
+            orders().save(&mut storage, id as u64, order).unwrap();
+        }
+        storage
+    }
+
+    #[test]
+    fn querying_by_asset_info() {
+        let storage = setup();
+
+        let ids = orders()
+            .idx
+            .offer
+            .prefix(AssetInfo::native("uatom").into())
+            .keys(&storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()
+            .unwrap();
+        assert_eq!(ids, vec![0, 3]);
+
+        let ids = orders()
+            .idx
+            .offer
+            .prefix(AssetInfo::native("uusd").into())
+            .keys(&storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()
+            .unwrap();
+        assert_eq!(ids, Vec::<u64>::new());
+    }
+
+    #[test]
+    fn querying_by_asset_type() {
+        let storage = setup();
+
+        let ids = orders()
+            .idx
+            .offer
+            .sub_prefix(NATIVE_TAG)
+            .keys(&storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()
+            .unwrap();
+        assert_eq!(ids, vec![0, 3, 2]);
+
+        let items = orders()
+            .idx
+            .offer
+            .sub_prefix(CW20_TAG)
+            .range(&storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()
+            .unwrap();
+        assert_eq!(items, vec![(1, mock_orders()[1].clone())]);
+    }
+
+    #[test]
+    fn updating_index() {
+        let mut storage = setup();
+
+        let mut order = mock_orders()[1].clone();
+        order.offer = Asset::native("uusd", 200u128);
+        orders().save(&mut storage, 1, &order).unwrap();
+
+        let count = orders()
+            .idx
+            .offer
+            .sub_prefix(CW20_TAG)
+            .keys(&storage, None, None, Order::Ascending)
+            .count();
+        assert_eq!(count, 0);
+
+        orders().remove(&mut storage, 1).unwrap();
+
+        let count = orders()
+            .idx
+            .offer
+            .sub_prefix(NATIVE_TAG)
+            .keys(&storage, None, None, Order::Ascending)
+            .count();
+        assert_eq!(count, 3);
+    }
+}
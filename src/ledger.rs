@@ -0,0 +1,322 @@
+use cosmwasm_std::{Addr, Order, StdResult, Storage, Uint128};
+use cw_storage_plus::{Bound, Map};
+
+use crate::{Asset, AssetError, AssetInfo, AssetList};
+
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
+
+/// A storage primitive that tracks how much of each asset every owner holds
+///
+/// The ledger is made up of three maps, each under its own namespace:
+///
+/// - balances, keyed by owner and then asset info, so that an owner's full
+///   balance can be loaded with a single prefix range;
+/// - holders, keyed by asset info and then owner, which mirrors the balances so
+///   that the holders of an asset can be paginated;
+/// - totals, keyed by asset info, holding the sum of all owners' balances.
+///
+/// Balances that are reduced to zero are removed from storage.
+///
+/// ```rust
+/// use cosmwasm_std::{Addr, Storage};
+/// use cw_asset::{Asset, AssetError, AssetLedger};
+///
+/// const DEPOSITS: AssetLedger = AssetLedger::new("deposits", "depositors", "total_deposits");
+///
+/// fn deposit(storage: &mut dyn Storage, user: &Addr, asset: &Asset) -> Result<(), AssetError> {
+///     DEPOSITS.credit(storage, user, asset)?;
+///     Ok(())
+/// }
+/// ```
+pub struct AssetLedger {
+    balances_namespace: &'static str,
+    holders_namespace: &'static str,
+    totals_namespace: &'static str,
+}
+
+impl AssetLedger {
+    /// Create a new **asset ledger** instance, given the namespaces of the
+    /// balances, holders and totals maps
+    pub const fn new(
+        balances_namespace: &'static str,
+        holders_namespace: &'static str,
+        totals_namespace: &'static str,
+    ) -> Self {
+        Self {
+            balances_namespace,
+            holders_namespace,
+            totals_namespace,
+        }
+    }
+
+    fn balances<'a>(&self) -> Map<(&'a Addr, &'a AssetInfo), Uint128> {
+        Map::new(self.balances_namespace)
+    }
+
+    fn holders<'a>(&self) -> Map<(&'a AssetInfo, &'a Addr), Uint128> {
+        Map::new(self.holders_namespace)
+    }
+
+    fn totals<'a>(&self) -> Map<&'a AssetInfo, Uint128> {
+        Map::new(self.totals_namespace)
+    }
+
+    /// Write an owner's balance of an asset, and its mirror in the holders map;
+    /// remove both entries if the balance is zero
+    fn set_balance(
+        &self,
+        storage: &mut dyn Storage,
+        owner: &Addr,
+        info: &AssetInfo,
+        amount: Uint128,
+    ) -> StdResult<()> {
+        if amount.is_zero() {
+            self.balances().remove(storage, (owner, info));
+            self.holders().remove(storage, (info, owner));
+        } else {
+            self.balances().save(storage, (owner, info), &amount)?;
+            self.holders().save(storage, (info, owner), &amount)?;
+        }
+        Ok(())
+    }
+
+    fn set_total(
+        &self,
+        storage: &mut dyn Storage,
+        info: &AssetInfo,
+        amount: Uint128,
+    ) -> StdResult<()> {
+        if amount.is_zero() {
+            self.totals().remove(storage, info);
+            Ok(())
+        } else {
+            self.totals().save(storage, info, &amount)
+        }
+    }
+
+    /// Return an owner's balance of an asset; zero if the owner does not hold
+    /// the asset
+    pub fn balance(
+        &self,
+        storage: &dyn Storage,
+        owner: &Addr,
+        info: &AssetInfo,
+    ) -> StdResult<Uint128> {
+        self.balances().may_load(storage, (owner, info)).map(Option::unwrap_or_default)
+    }
+
+    /// Return all assets held by an owner
+    pub fn balances_of(&self, storage: &dyn Storage, owner: &Addr) -> StdResult<AssetList> {
+        self.balances()
+            .prefix(owner)
+            .range(storage, None, None, Order::Ascending)
+            .map(|item| item.map(|(info, amount)| Asset::new(info, amount)))
+            .collect::<StdResult<Vec<_>>>()
+            .map(AssetList::from)
+    }
+
+    /// Return the sum of all owners' balances of an asset
+    pub fn total(&self, storage: &dyn Storage, info: &AssetInfo) -> StdResult<Uint128> {
+        self.totals().may_load(storage, info).map(Option::unwrap_or_default)
+    }
+
+    /// Return the holders of an asset and their balances, paginated by the
+    /// holders' addresses in ascending order
+    pub fn holders_of(
+        &self,
+        storage: &dyn Storage,
+        info: &AssetInfo,
+        start_after: Option<&Addr>,
+        limit: Option<u32>,
+    ) -> StdResult<Vec<(Addr, Uint128)>> {
+        let start = start_after.map(Bound::exclusive);
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+
+        self.holders()
+            .prefix(info)
+            .range(storage, start, None, Order::Ascending)
+            .take(limit)
+            .collect()
+    }
+
+    /// Increase an owner's balance of an asset, as well as the asset's total;
+    /// return the owner's new balance
+    pub fn credit(
+        &self,
+        storage: &mut dyn Storage,
+        owner: &Addr,
+        asset: &Asset,
+    ) -> Result<Uint128, AssetError> {
+        let balance = self.balance(storage, owner, &asset.info)?.checked_add(asset.amount)?;
+        let total = self.total(storage, &asset.info)?.checked_add(asset.amount)?;
+
+        self.set_balance(storage, owner, &asset.info, balance)?;
+        self.set_total(storage, &asset.info, total)?;
+
+        Ok(balance)
+    }
+
+    /// Decrease an owner's balance of an asset, as well as the asset's total;
+    /// return the owner's new balance
+    ///
+    /// Returns an overflow error if the owner's balance is less than the amount
+    /// to be deducted.
+    pub fn debit(
+        &self,
+        storage: &mut dyn Storage,
+        owner: &Addr,
+        asset: &Asset,
+    ) -> Result<Uint128, AssetError> {
+        let balance = self.balance(storage, owner, &asset.info)?.checked_sub(asset.amount)?;
+        let total = self.total(storage, &asset.info)?.checked_sub(asset.amount)?;
+
+        self.set_balance(storage, owner, &asset.info, balance)?;
+        self.set_total(storage, &asset.info, total)?;
+
+        Ok(balance)
+    }
+
+    /// Increase an owner's balances of multiple assets
+    pub fn credit_many(
+        &self,
+        storage: &mut dyn Storage,
+        owner: &Addr,
+        assets: &AssetList,
+    ) -> Result<(), AssetError> {
+        for asset in assets {
+            self.credit(storage, owner, asset)?;
+        }
+        Ok(())
+    }
+
+    /// Decrease an owner's balances of multiple assets
+    pub fn debit_many(
+        &self,
+        storage: &mut dyn Storage,
+        owner: &Addr,
+        assets: &AssetList,
+    ) -> Result<(), AssetError> {
+        for asset in assets {
+            self.debit(storage, owner, asset)?;
+        }
+        Ok(())
+    }
+}
+
+//------------------------------------------------------------------------------
+// Tests
+//------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::{testing::MockStorage, OverflowError, OverflowOperation};
+
+    use super::*;
+    use crate::testing::mock_token;
+
+    const LEDGER: AssetLedger = AssetLedger::new("balances", "holders", "totals");
+
+    #[test]
+    fn crediting() {
+        let mut storage = MockStorage::default();
+        let alice = Addr::unchecked("alice");
+        let bob = Addr::unchecked("bob");
+
+        let balance = LEDGER.credit(&mut storage, &alice, &Asset::native("uusd", 100u128)).unwrap();
+        assert_eq!(balance, Uint128::new(100));
+
+        let balance = LEDGER.credit(&mut storage, &alice, &Asset::native("uusd", 50u128)).unwrap();
+        assert_eq!(balance, Uint128::new(150));
+
+        LEDGER.credit(&mut storage, &bob, &Asset::native("uusd", 25u128)).unwrap();
+
+        let uusd = AssetInfo::native("uusd");
+        assert_eq!(LEDGER.balance(&storage, &alice, &uusd).unwrap(), Uint128::new(150));
+        assert_eq!(LEDGER.balance(&storage, &bob, &uusd).unwrap(), Uint128::new(25));
+        assert_eq!(LEDGER.total(&storage, &uusd).unwrap(), Uint128::new(175));
+
+        let err = LEDGER.credit(&mut storage, &alice, &Asset::native("uusd", u128::MAX));
+        assert_eq!(err, Err(OverflowError::new(OverflowOperation::Add).into()));
+    }
+
+    #[test]
+    fn debiting() {
+        let mut storage = MockStorage::default();
+        let alice = Addr::unchecked("alice");
+        let uusd = AssetInfo::native("uusd");
+
+        LEDGER.credit(&mut storage, &alice, &Asset::native("uusd", 100u128)).unwrap();
+
+        let balance = LEDGER.debit(&mut storage, &alice, &Asset::native("uusd", 60u128)).unwrap();
+        assert_eq!(balance, Uint128::new(40));
+        assert_eq!(LEDGER.total(&storage, &uusd).unwrap(), Uint128::new(40));
+
+        let err = LEDGER.debit(&mut storage, &alice, &Asset::native("uusd", 41u128));
+        assert_eq!(err, Err(OverflowError::new(OverflowOperation::Sub).into()));
+
+        let err = LEDGER.debit(&mut storage, &alice, &Asset::new(mock_token(), 1u128));
+        assert_eq!(err, Err(OverflowError::new(OverflowOperation::Sub).into()));
+
+        // balances reduced to zero are removed
+        LEDGER.debit(&mut storage, &alice, &Asset::native("uusd", 40u128)).unwrap();
+        assert_eq!(LEDGER.balances_of(&storage, &alice).unwrap(), AssetList::new());
+        assert_eq!(LEDGER.holders_of(&storage, &uusd, None, None).unwrap(), vec![]);
+        assert_eq!(LEDGER.total(&storage, &uusd).unwrap(), Uint128::zero());
+    }
+
+    #[test]
+    fn crediting_and_debiting_many() {
+        let mut storage = MockStorage::default();
+        let alice = Addr::unchecked("alice");
+
+        let list = AssetList::from(vec![
+            Asset::native("uusd", 100u128),
+            Asset::new(mock_token(), 200u128),
+        ]);
+        LEDGER.credit_many(&mut storage, &alice, &list).unwrap();
+        LEDGER.credit_many(&mut storage, &alice, &list).unwrap();
+
+        assert_eq!(
+            LEDGER.balances_of(&storage, &alice).unwrap(),
+            AssetList::from(vec![
+                Asset::new(mock_token(), 400u128),
+                Asset::native("uusd", 200u128)
+            ]),
+        );
+
+        LEDGER.debit_many(&mut storage, &alice, &list).unwrap();
+
+        assert_eq!(
+            LEDGER.balances_of(&storage, &alice).unwrap(),
+            AssetList::from(vec![
+                Asset::new(mock_token(), 200u128),
+                Asset::native("uusd", 100u128)
+            ]),
+        );
+        assert_eq!(LEDGER.total(&storage, &mock_token()).unwrap(), Uint128::new(200));
+    }
+
+    #[test]
+    fn paginating_holders() {
+        let mut storage = MockStorage::default();
+        let asset = Asset::new(mock_token(), 1u128);
+
+        let users = (0..35).map(|i| Addr::unchecked(format!("user{i:02}"))).collect::<Vec<_>>();
+        for user in &users {
+            LEDGER.credit(&mut storage, user, &asset).unwrap();
+        }
+
+        let holders = LEDGER.holders_of(&storage, &mock_token(), None, None).unwrap();
+        assert_eq!(holders.len(), 10);
+        assert_eq!(holders[0], (users[0].clone(), Uint128::one()));
+
+        let holders =
+            LEDGER.holders_of(&storage, &mock_token(), Some(&users[9]), Some(100)).unwrap();
+        assert_eq!(holders.len(), 25);
+        assert_eq!(holders[0], (users[10].clone(), Uint128::one()));
+        assert_eq!(holders[24], (users[34].clone(), Uint128::one()));
+
+        assert_eq!(LEDGER.total(&storage, &mock_token()).unwrap(), Uint128::new(35));
+    }
+}
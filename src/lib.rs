@@ -78,16 +78,95 @@
 //!     Ok(())
 //! }
 //! ```
+//!
+//! ## Testing
+//!
+//! With the `testing` feature enabled, the [`testing`] module provides mock dependencies whose
+//! querier answers bank and CW20 queries, so that methods such as [`AssetInfo::query_balance`]
+//! can be unit-tested without a real chain:
+//!
+//! ```toml
+//! [dev-dependencies]
+//! cw-asset = { version = "4", features = ["testing"] }
+//! ```
+//!
+//! For integration tests, the `multi-test` feature provides the [`multi_test::AssetAppExt`]
+//! trait, which extends cw-multi-test's `App` with methods for creating CW20 tokens, minting and
+//! sending assets, and asserting balances.
+//!
+//! For fuzzing, the `proptest` feature provides the [`arbitrary`] module, with strategies for
+//! generating random but valid assets.
+//!
+//! ## Protobuf
+//!
+//! With the `protobuf` feature enabled, the [`proto`] module converts native coins to and from
+//! their Cosmos SDK protobuf encoding, and builds protobuf-encoded messages such as `MsgSend`,
+//! including authz `MsgExec` messages for transferring assets on behalf of a granter.
+//!
+//! ## JSON schema
+//!
+//! The [`schema`] module provides JSON schemas of the unchecked types, named `AssetInfoUnchecked`,
+//! `AssetUnchecked` and `AssetListUnchecked` rather than after their generic parameters, for use by
+//! frontends and code generators. The generated schemas are checked in under `schema/`.
+
+// without their features, link the feature-gated items to their docs on docs.rs;
+// the empty doc line separates the link definitions from the paragraph above
+#![doc = ""]
+#![cfg_attr(
+    not(feature = "testing"),
+    doc = "[`testing`]: https://docs.rs/cw-asset/latest/cw_asset/testing/index.html"
+)]
+#![cfg_attr(
+    not(feature = "multi-test"),
+    doc = "[`multi_test::AssetAppExt`]: \
+           https://docs.rs/cw-asset/latest/cw_asset/multi_test/trait.AssetAppExt.html"
+)]
+#![cfg_attr(
+    not(feature = "proptest"),
+    doc = "[`arbitrary`]: https://docs.rs/cw-asset/latest/cw_asset/arbitrary/index.html"
+)]
+#![cfg_attr(
+    not(feature = "protobuf"),
+    doc = "[`proto`]: https://docs.rs/cw-asset/latest/cw_asset/proto/index.html"
+)]
 
+#[cfg(feature = "proptest")]
+pub mod arbitrary;
 mod asset;
 mod asset_info;
 mod asset_list;
+mod compact_key;
+mod conversion;
 mod error;
+mod event;
+mod flexible;
+mod guard;
+mod hook;
+mod index;
+mod ledger;
+mod price;
+#[cfg(feature = "protobuf")]
+pub mod proto;
+mod received;
+pub mod schema;
+mod slippage;
 
 pub use asset::{Asset, AssetBase, AssetUnchecked};
 pub use asset_info::{AssetInfo, AssetInfoBase, AssetInfoUnchecked};
-pub use asset_list::{AssetList, AssetListBase, AssetListUnchecked};
+pub use asset_list::{AssetList, AssetListBase, AssetListUnchecked, Cw20Delivery};
+pub use compact_key::{migrate_to_compact_keys, CompactAssetInfo, CW20_TAG, NATIVE_TAG};
+pub use conversion::Rounding;
 pub use error::AssetError;
+pub use event::{AssetEvent, AssetEventKind};
+pub use flexible::{Flexible, FlexibleParse};
+pub use guard::{BalanceGuard, BalanceSnapshot};
+pub use hook::{ReceiveMsg, Received};
+pub use index::{asset_info_index, HasAssetInfo};
+pub use ledger::AssetLedger;
+pub use price::{PriceSource, QuerierPriceSource, StaticPriceSource};
+pub use slippage::SlippageViolation;
 
-#[cfg(test)]
-mod testing;
+#[cfg(feature = "multi-test")]
+pub mod multi_test;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
@@ -0,0 +1,324 @@
+//! Helpers for integration-testing contracts that interact with assets using
+//! [cw-multi-test](https://crates.io/crates/cw-multi-test)
+//!
+//! Available with the `multi-test` feature.
+
+use cosmwasm_schema::serde::de::DeserializeOwned;
+use cosmwasm_std::{Addr, Api, Coin, CustomMsg, CustomQuery, QuerierWrapper, Storage};
+use cw20::{Cw20ExecuteMsg, Cw20QueryMsg, MinterResponse};
+use cw_multi_test::{
+    error::{bail, AnyResult},
+    App, Bank, BankSudo, ContractWrapper, Distribution, Executor, Gov, Ibc, Module, Staking,
+    Stargate, SudoMsg, Wasm,
+};
+
+use crate::{Asset, AssetError, AssetInfo, AssetList};
+
+/// Extension methods for cw-multi-test's `App` for creating, minting, sending
+/// and asserting balances of assets
+///
+/// Balances are always queried using [`AssetInfo::query_balance`], so that test
+/// assertions go through the same code path as contracts do.
+///
+/// Implemented for any `App`, including ones built with custom messages,
+/// queries or modules.
+///
+/// ```rust
+/// use cosmwasm_std::Coin;
+/// use cw_asset::{multi_test::AssetAppExt, Asset, AssetList};
+/// use cw_multi_test::App;
+///
+/// let mut app = App::default();
+/// let minter = app.api().addr_make("minter");
+/// let alice = app.api().addr_make("alice");
+///
+/// let token = app.create_cw20(&minter, "MOCK", 6).unwrap();
+///
+/// let assets =
+///     AssetList::from(vec![Asset::native("uatom", 12345u128), Asset::new(token, 67890u128)]);
+/// app.mint_assets(&alice, &assets).unwrap();
+///
+/// app.assert_asset_balances(&alice, &assets);
+/// ```
+pub trait AssetAppExt {
+    /// Store the cw20-base contract code, and return its code id
+    fn store_cw20_code(&mut self) -> u64;
+
+    /// Instantiate a cw20-base contract from the given code id, with `minter`
+    /// as the minter and admin, and return the token's **asset info**
+    ///
+    /// The symbol is also used as the token's name; it must therefore consist
+    /// of 3-12 letters.
+    fn instantiate_cw20(
+        &mut self,
+        code_id: u64,
+        minter: &Addr,
+        symbol: &str,
+        decimals: u8,
+    ) -> AnyResult<AssetInfo>;
+
+    /// Store the cw20-base contract code and instantiate a token from it
+    fn create_cw20(&mut self, minter: &Addr, symbol: &str, decimals: u8) -> AnyResult<AssetInfo> {
+        let code_id = self.store_cw20_code();
+        self.instantiate_cw20(code_id, minter, symbol, decimals)
+    }
+
+    /// Mint an **asset** to a recipient
+    ///
+    /// Native coins are minted by the bank module. CW20 tokens are minted on
+    /// behalf of the token's minter, which must therefore be set.
+    fn mint_asset(&mut self, recipient: &Addr, asset: &Asset) -> AnyResult<()>;
+
+    /// Mint each **asset** in an **asset list** to a recipient
+    fn mint_assets(&mut self, recipient: &Addr, assets: &AssetList) -> AnyResult<()> {
+        for asset in assets {
+            self.mint_asset(recipient, asset)?;
+        }
+        Ok(())
+    }
+
+    /// Fund a recipient with an **asset** held by `sender`, by executing the
+    /// message generated by [`Asset::transfer_msg`]
+    fn fund_asset(&mut self, sender: &Addr, recipient: &Addr, asset: &Asset) -> AnyResult<()>;
+
+    /// Fund a recipient with each **asset** in an **asset list** held by
+    /// `sender`, by executing the messages generated by
+    /// [`AssetList::transfer_msgs`]
+    fn fund_assets(
+        &mut self,
+        sender: &Addr,
+        recipient: &Addr,
+        assets: &AssetList,
+    ) -> AnyResult<()> {
+        for asset in assets {
+            self.fund_asset(sender, recipient, asset)?;
+        }
+        Ok(())
+    }
+
+    /// Query an address' balance of each of the given assets
+    fn query_asset_balances(
+        &self,
+        address: &Addr,
+        infos: &[AssetInfo],
+    ) -> Result<AssetList, AssetError>;
+
+    /// Assert that an address holds exactly the amount of each **asset** in
+    /// an **asset list**
+    ///
+    /// Panics with the mismatching asset's expected and actual amounts if the
+    /// assertion fails. Assets not in the list are not checked.
+    fn assert_asset_balances(&self, address: &Addr, expected: &AssetList) {
+        for asset in expected {
+            let actual = self
+                .query_asset_balances(address, std::slice::from_ref(&asset.info))
+                .unwrap_or_else(|err| panic!("failed to query balance of {}: {err}", asset.info));
+            let amount = actual.find(&asset.info).map(|a| a.amount).unwrap_or_default();
+            assert_eq!(
+                amount, asset.amount,
+                "balance mismatch of {} for {address}: expected {}, got {amount}",
+                asset.info, asset.amount,
+            );
+        }
+    }
+}
+
+impl<BankT, ApiT, StorageT, CustomT, WasmT, StakingT, DistrT, IbcT, GovT, StargateT> AssetAppExt
+    for App<BankT, ApiT, StorageT, CustomT, WasmT, StakingT, DistrT, IbcT, GovT, StargateT>
+where
+    CustomT::ExecT: CustomMsg + DeserializeOwned + 'static,
+    CustomT::QueryT: CustomQuery + DeserializeOwned + 'static,
+    WasmT: Wasm<CustomT::ExecT, CustomT::QueryT>,
+    BankT: Bank,
+    ApiT: Api,
+    StorageT: Storage,
+    CustomT: Module,
+    StakingT: Staking,
+    DistrT: Distribution,
+    IbcT: Ibc,
+    GovT: Gov,
+    StargateT: Stargate,
+{
+    fn store_cw20_code(&mut self) -> u64 {
+        self.store_code(Box::new(ContractWrapper::new_with_empty(
+            cw20_base::contract::execute,
+            cw20_base::contract::instantiate,
+            cw20_base::contract::query,
+        )))
+    }
+
+    fn instantiate_cw20(
+        &mut self,
+        code_id: u64,
+        minter: &Addr,
+        symbol: &str,
+        decimals: u8,
+    ) -> AnyResult<AssetInfo> {
+        let contract_addr = self.instantiate_contract(
+            code_id,
+            minter.clone(),
+            &cw20_base::msg::InstantiateMsg {
+                name: symbol.into(),
+                symbol: symbol.into(),
+                decimals,
+                initial_balances: vec![],
+                mint: Some(cw20::MinterResponse {
+                    minter: minter.into(),
+                    cap: None,
+                }),
+                marketing: None,
+            },
+            &[],
+            symbol,
+            Some(minter.into()),
+        )?;
+        Ok(AssetInfo::cw20(contract_addr))
+    }
+
+    fn mint_asset(&mut self, recipient: &Addr, asset: &Asset) -> AnyResult<()> {
+        match &asset.info {
+            AssetInfo::Native(denom) => {
+                self.sudo(SudoMsg::Bank(BankSudo::Mint {
+                    to_address: recipient.into(),
+                    amount: vec![Coin::new(asset.amount, denom)],
+                }))?;
+            },
+            AssetInfo::Cw20(contract_addr) => {
+                let minter: Option<MinterResponse> =
+                    self.wrap().query_wasm_smart(contract_addr, &Cw20QueryMsg::Minter {})?;
+                let Some(minter) = minter else {
+                    bail!("cw20 token {contract_addr} does not have a minter");
+                };
+                self.execute_contract(
+                    Addr::unchecked(minter.minter),
+                    contract_addr.clone(),
+                    &Cw20ExecuteMsg::Mint {
+                        recipient: recipient.into(),
+                        amount: asset.amount,
+                    },
+                    &[],
+                )?;
+            },
+        }
+        Ok(())
+    }
+
+    fn fund_asset(&mut self, sender: &Addr, recipient: &Addr, asset: &Asset) -> AnyResult<()> {
+        let Some(msg) = asset.transfer_msg(recipient)?.change_custom() else {
+            bail!("transfer message of asset {asset} is a custom message");
+        };
+        self.execute(sender.clone(), msg)?;
+        Ok(())
+    }
+
+    fn query_asset_balances(
+        &self,
+        address: &Addr,
+        infos: &[AssetInfo],
+    ) -> Result<AssetList, AssetError> {
+        infos
+            .iter()
+            .map(|info| {
+                info.query_balance(&QuerierWrapper::new(self), address)
+                    .map(|amount| Asset::new(info.clone(), amount))
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map(AssetList::from)
+    }
+}
+
+//------------------------------------------------------------------------------
+// Tests
+//------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn minting_and_funding() {
+        let mut app = App::default();
+        let minter = app.api().addr_make("minter");
+        let alice = app.api().addr_make("alice");
+        let bob = app.api().addr_make("bob");
+
+        let token = app.create_cw20(&minter, "MOCK", 6).unwrap();
+
+        let assets = AssetList::from(vec![
+            Asset::native("uatom", 100u128),
+            Asset::new(token.clone(), 200u128),
+        ]);
+        app.mint_assets(&alice, &assets).unwrap();
+        app.assert_asset_balances(&alice, &assets);
+
+        let sent = AssetList::from(vec![
+            Asset::native("uatom", 40u128),
+            Asset::new(token.clone(), 50u128),
+        ]);
+        app.fund_assets(&alice, &bob, &sent).unwrap();
+
+        app.assert_asset_balances(
+            &alice,
+            &AssetList::from(vec![
+                Asset::native("uatom", 60u128),
+                Asset::new(token.clone(), 150u128),
+            ]),
+        );
+        app.assert_asset_balances(&bob, &sent);
+
+        let balances =
+            app.query_asset_balances(&bob, &[AssetInfo::native("uosmo"), token.clone()]).unwrap();
+        assert_eq!(
+            balances,
+            AssetList::from(vec![Asset::native("uosmo", 0u128), Asset::new(token.clone(), 50u128)]),
+        );
+
+        // insufficient funds
+        let err = app.fund_asset(&bob, &alice, &Asset::new(token, 51u128));
+        assert!(err.is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "balance mismatch")]
+    fn asserting_wrong_balance() {
+        let mut app = App::default();
+        let alice = app.api().addr_make("alice");
+
+        app.mint_asset(&alice, &Asset::native("uatom", 100u128)).unwrap();
+        app.assert_asset_balances(&alice, &AssetList::from(vec![Asset::native("uatom", 101u128)]));
+    }
+
+    #[test]
+    fn minting_and_funding_in_custom_app() {
+        use cosmwasm_schema::cw_serde;
+        use cosmwasm_std::Empty;
+        use cw_multi_test::{custom_app, no_init};
+
+        #[cw_serde]
+        enum MockCustomMsg {
+            Noop {},
+        }
+
+        impl CustomMsg for MockCustomMsg {}
+
+        let mut app = custom_app::<MockCustomMsg, Empty, _>(no_init);
+        let minter = app.api().addr_make("minter");
+        let alice = app.api().addr_make("alice");
+        let bob = app.api().addr_make("bob");
+
+        let token = app.create_cw20(&minter, "MOCK", 6).unwrap();
+
+        let assets = AssetList::from(vec![
+            Asset::native("uatom", 100u128),
+            Asset::new(token.clone(), 200u128),
+        ]);
+        app.mint_assets(&alice, &assets).unwrap();
+        app.fund_assets(&alice, &bob, &assets).unwrap();
+
+        app.assert_asset_balances(
+            &alice,
+            &AssetList::from(vec![Asset::native("uatom", 0u128), Asset::new(token, 0u128)]),
+        );
+        app.assert_asset_balances(&bob, &assets);
+    }
+}
@@ -0,0 +1,292 @@
+use std::collections::BTreeMap;
+
+use cosmwasm_std::{Decimal, QuerierWrapper, StdResult, Uint128};
+
+use crate::{conversion::mul_fraction, Asset, AssetError, AssetInfo, AssetList, Rounding};
+
+/// A source of asset prices, each denominated in a common quote asset
+///
+/// A price is the amount of the quote asset that one unit of the asset is worth,
+/// both in their smallest units. E.g. if the quote asset is `uusd`, the price of
+/// `uatom` at 10 USD per ATOM, both with 6 decimals, is `10`.
+pub trait PriceSource {
+    /// Return the price of the given asset
+    ///
+    /// Returns an `AssetError::PriceNotFound` error if the price is unknown.
+    fn price(&self, info: &AssetInfo) -> Result<Decimal, AssetError>;
+}
+
+/// A **price source** backed by a fixed map of prices
+///
+/// ```rust
+/// use cosmwasm_std::Decimal;
+/// use cw_asset::{AssetInfo, PriceSource, StaticPriceSource};
+///
+/// let prices = StaticPriceSource::new()
+///     .with_price(AssetInfo::native("uusd"), Decimal::one())
+///     .with_price(AssetInfo::native("uatom"), Decimal::percent(1000));
+///
+/// assert_eq!(prices.price(&AssetInfo::native("uatom")).unwrap(), Decimal::percent(1000));
+/// assert!(prices.price(&AssetInfo::native("uosmo")).is_err());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StaticPriceSource(BTreeMap<AssetInfo, Decimal>);
+
+impl StaticPriceSource {
+    /// Create a new, empty **price source**
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the price of an asset, replacing any existing one, and return the
+    /// **price source**
+    pub fn with_price(mut self, info: AssetInfo, price: Decimal) -> Self {
+        self.set_price(info, price);
+        self
+    }
+
+    /// Set the price of an asset, replacing any existing one
+    pub fn set_price(&mut self, info: AssetInfo, price: Decimal) -> &mut Self {
+        self.0.insert(info, price);
+        self
+    }
+}
+
+impl FromIterator<(AssetInfo, Decimal)> for StaticPriceSource {
+    fn from_iter<I: IntoIterator<Item = (AssetInfo, Decimal)>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl From<Vec<(AssetInfo, Decimal)>> for StaticPriceSource {
+    fn from(prices: Vec<(AssetInfo, Decimal)>) -> Self {
+        prices.into_iter().collect()
+    }
+}
+
+impl PriceSource for StaticPriceSource {
+    fn price(&self, info: &AssetInfo) -> Result<Decimal, AssetError> {
+        self.0.get(info).copied().ok_or_else(|| AssetError::PriceNotFound {
+            info: info.to_string(),
+        })
+    }
+}
+
+/// A **price source** that queries prices from the chain, e.g. from an oracle
+/// contract
+///
+/// The query is performed by the given function, which returns `None` if the
+/// price of the asset is unknown.
+///
+/// ```rust
+/// use cosmwasm_schema::cw_serde;
+/// use cosmwasm_std::{Decimal, Deps, Uint128};
+/// use cw_asset::{AssetError, AssetInfo, AssetList, QuerierPriceSource};
+///
+/// #[cw_serde]
+/// enum OracleQueryMsg {
+///     Price {
+///         asset: AssetInfo,
+///     },
+/// }
+///
+/// fn total_collateral(deps: Deps, collaterals: &AssetList) -> Result<Uint128, AssetError> {
+///     let oracle = QuerierPriceSource::new(deps.querier, |querier, info| {
+///         querier.query_wasm_smart::<Option<Decimal>>(
+///             "oracle_addr",
+///             &OracleQueryMsg::Price {
+///                 asset: info.clone(),
+///             },
+///         )
+///     });
+///
+///     collaterals.value(&oracle)
+/// }
+/// ```
+pub struct QuerierPriceSource<'a, F> {
+    querier: QuerierWrapper<'a>,
+    query_fn: F,
+}
+
+impl<'a, F> QuerierPriceSource<'a, F>
+where
+    F: Fn(&QuerierWrapper<'a>, &AssetInfo) -> StdResult<Option<Decimal>>,
+{
+    /// Create a new **price source**, given the querier and the function that
+    /// queries the price of an asset
+    pub fn new(querier: QuerierWrapper<'a>, query_fn: F) -> Self {
+        Self {
+            querier,
+            query_fn,
+        }
+    }
+}
+
+impl<'a, F> PriceSource for QuerierPriceSource<'a, F>
+where
+    F: Fn(&QuerierWrapper<'a>, &AssetInfo) -> StdResult<Option<Decimal>>,
+{
+    fn price(&self, info: &AssetInfo) -> Result<Decimal, AssetError> {
+        (self.query_fn)(&self.querier, info)?.ok_or_else(|| AssetError::PriceNotFound {
+            info: info.to_string(),
+        })
+    }
+}
+
+impl Asset {
+    /// Return the value of the **asset** in the quote asset of the given
+    /// **price source**, rounded down
+    ///
+    /// Returns an `AssetError::PriceNotFound` error if the price of the asset is
+    /// unknown, or an `AssetError::Overflow` error if the value does not fit in
+    /// `Uint128`.
+    ///
+    /// ```rust
+    /// use cosmwasm_std::{Decimal, Uint128};
+    /// use cw_asset::{Asset, AssetInfo, StaticPriceSource};
+    ///
+    /// let prices =
+    ///     StaticPriceSource::from(vec![(AssetInfo::native("uatom"), Decimal::percent(1050))]);
+    ///
+    /// let asset = Asset::native("uatom", 12345u128);
+    /// assert_eq!(asset.value(&prices).unwrap(), Uint128::new(129622));
+    /// ```
+    pub fn value(&self, source: &impl PriceSource) -> Result<Uint128, AssetError> {
+        let price = source.price(&self.info)?;
+        mul_fraction(self.amount, price, Rounding::Floor)
+    }
+}
+
+impl AssetList {
+    /// Return the total value of the **asset list** in the quote asset of the
+    /// given **price source**
+    ///
+    /// Each asset's value is rounded down before being summed up. Returns error
+    /// if the price of any asset is unknown, or if the total overflows.
+    ///
+    /// ```rust
+    /// use cosmwasm_std::{Decimal, Uint128};
+    /// use cw_asset::{Asset, AssetInfo, AssetList, StaticPriceSource};
+    ///
+    /// let prices = StaticPriceSource::new()
+    ///     .with_price(AssetInfo::native("uusd"), Decimal::one())
+    ///     .with_price(AssetInfo::native("uatom"), Decimal::percent(1050));
+    ///
+    /// let list =
+    ///     AssetList::from(vec![Asset::native("uusd", 10000u128), Asset::native("uatom", 12345u128)]);
+    /// assert_eq!(list.value(&prices).unwrap(), Uint128::new(139622));
+    /// ```
+    pub fn value(&self, source: &impl PriceSource) -> Result<Uint128, AssetError> {
+        self.into_iter()
+            .try_fold(Uint128::zero(), |total, asset| Ok(total.checked_add(asset.value(source)?)?))
+    }
+
+    /// Return the value of each **asset** in the **asset list**, in the quote
+    /// asset of the given **price source**, in the order of the list
+    ///
+    /// Returns error if the price of any asset is unknown, or if any value
+    /// overflows.
+    pub fn value_breakdown(
+        &self,
+        source: &impl PriceSource,
+    ) -> Result<Vec<(AssetInfo, Uint128)>, AssetError> {
+        self.into_iter().map(|asset| Ok((asset.info.clone(), asset.value(source)?))).collect()
+    }
+}
+
+//------------------------------------------------------------------------------
+// Tests
+//------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::StdError;
+
+    use super::*;
+    use crate::testing::{mock_token, CustomMockQuerier};
+
+    fn mock_prices() -> StaticPriceSource {
+        StaticPriceSource::from(vec![
+            (AssetInfo::native("uusd"), Decimal::one()),
+            (AssetInfo::native("uatom"), Decimal::percent(1050)),
+            (mock_token(), Decimal::permille(5)),
+        ])
+    }
+
+    #[test]
+    fn static_prices() {
+        let mut prices = mock_prices();
+        assert_eq!(prices.price(&mock_token()).unwrap(), Decimal::permille(5));
+        assert_eq!(
+            prices.price(&AssetInfo::native("uosmo")),
+            Err(AssetError::PriceNotFound {
+                info: "native:uosmo".into(),
+            }),
+        );
+
+        prices.set_price(mock_token(), Decimal::percent(2));
+        assert_eq!(prices.price(&mock_token()).unwrap(), Decimal::percent(2));
+    }
+
+    #[test]
+    fn querier_prices() {
+        let querier = CustomMockQuerier::default();
+        let prices = QuerierPriceSource::new(QuerierWrapper::new(&querier), |_, info| match info {
+            AssetInfo::Native(denom) if denom == "uatom" => Ok(Some(Decimal::percent(1050))),
+            AssetInfo::Native(denom) if denom == "uosmo" => Ok(None),
+            _ => Err(StdError::generic_err("oracle is down")),
+        });
+
+        assert_eq!(prices.price(&AssetInfo::native("uatom")).unwrap(), Decimal::percent(1050));
+        assert_eq!(
+            prices.price(&AssetInfo::native("uosmo")),
+            Err(AssetError::PriceNotFound {
+                info: "native:uosmo".into(),
+            }),
+        );
+        assert_eq!(
+            prices.price(&mock_token()),
+            Err(StdError::generic_err("oracle is down").into()),
+        );
+    }
+
+    #[test]
+    fn valuing_assets() {
+        let prices = mock_prices();
+
+        assert_eq!(Asset::native("uatom", 12345u128).value(&prices).unwrap(), Uint128::new(129622));
+        assert_eq!(Asset::new(mock_token(), 199u128).value(&prices).unwrap(), Uint128::zero());
+
+        let err = Asset::native("uatom", u128::MAX).value(&prices).unwrap_err();
+        assert!(matches!(err, AssetError::Overflow(_)));
+
+        let list = AssetList::from(vec![
+            Asset::native("uusd", 10000u128),
+            Asset::native("uatom", 12345u128),
+            Asset::new(mock_token(), 1000000u128),
+        ]);
+        assert_eq!(list.value(&prices).unwrap(), Uint128::new(144622));
+        assert_eq!(
+            list.value_breakdown(&prices).unwrap(),
+            vec![
+                (AssetInfo::native("uusd"), Uint128::new(10000)),
+                (AssetInfo::native("uatom"), Uint128::new(129622)),
+                (mock_token(), Uint128::new(5000)),
+            ],
+        );
+        assert_eq!(AssetList::new().value(&prices).unwrap(), Uint128::zero());
+
+        let list =
+            AssetList::from(vec![Asset::native("uusd", u128::MAX), Asset::native("uusd", 1u128)]);
+        assert!(matches!(list.value(&prices).unwrap_err(), AssetError::Overflow(_)));
+
+        let list =
+            AssetList::from(vec![Asset::native("uusd", 1u128), Asset::native("uosmo", 1u128)]);
+        assert_eq!(
+            list.value_breakdown(&prices),
+            Err(AssetError::PriceNotFound {
+                info: "native:uosmo".into(),
+            }),
+        );
+    }
+}
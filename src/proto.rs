@@ -0,0 +1,637 @@
+//! Conversions between assets and their Cosmos SDK protobuf representations,
+//! and builders of protobuf-encoded messages
+//!
+//! Available with the `protobuf` feature. Since the messages are dispatched as
+//! `CosmosMsg::Any`, this feature also enables cosmwasm-std's `cosmwasm_2_0`
+//! feature, meaning contracts using it require a chain running CosmWasm 2.0 or
+//! later.
+//!
+//! Besides bank sends, this module builds authz `MsgExec` messages, allowing a
+//! contract to move native coins on behalf of a user who has granted it a
+//! send authorization.
+//!
+//! Only native coins have a protobuf representation. Converting an **asset**
+//! representing a CW20 token returns an `AssetError::CannotCastToStdCoin`
+//! error.
+
+use cosmwasm_std::{AnyMsg, Binary, Coin, CosmosMsg, StdError, Uint128};
+use prost::Message;
+
+use crate::{Asset, AssetError, AssetInfo, AssetList};
+
+/// The protobuf type of a native coin, `cosmos.base.v1beta1.Coin`
+#[derive(Clone, PartialEq, Eq, prost::Message)]
+pub struct ProtoCoin {
+    #[prost(string, tag = "1")]
+    pub denom: String,
+    #[prost(string, tag = "2")]
+    pub amount: String,
+}
+
+/// A protobuf message whose first field is a list of native coins, i.e.
+/// `repeated cosmos.base.v1beta1.Coin balances = 1`, such as the bank module's
+/// `QueryAllBalancesResponse`
+#[derive(Clone, PartialEq, Eq, prost::Message)]
+struct ProtoCoins {
+    #[prost(message, repeated, tag = "1")]
+    balances: Vec<ProtoCoin>,
+}
+
+/// The protobuf type of the bank module's send message,
+/// `cosmos.bank.v1beta1.MsgSend`
+#[derive(Clone, PartialEq, Eq, prost::Message)]
+pub struct MsgSend {
+    #[prost(string, tag = "1")]
+    pub from_address: String,
+    #[prost(string, tag = "2")]
+    pub to_address: String,
+    #[prost(message, repeated, tag = "3")]
+    pub amount: Vec<ProtoCoin>,
+}
+
+impl MsgSend {
+    pub const TYPE_URL: &'static str = "/cosmos.bank.v1beta1.MsgSend";
+}
+
+impl From<MsgSend> for AnyMsg {
+    fn from(msg: MsgSend) -> Self {
+        AnyMsg {
+            type_url: MsgSend::TYPE_URL.into(),
+            value: msg.encode_to_vec().into(),
+        }
+    }
+}
+
+/// The protobuf type of an encoded message of arbitrary type,
+/// `google.protobuf.Any`
+#[derive(Clone, PartialEq, Eq, prost::Message)]
+pub struct ProtoAny {
+    #[prost(string, tag = "1")]
+    pub type_url: String,
+    #[prost(bytes = "vec", tag = "2")]
+    pub value: Vec<u8>,
+}
+
+impl From<AnyMsg> for ProtoAny {
+    fn from(msg: AnyMsg) -> Self {
+        ProtoAny {
+            type_url: msg.type_url,
+            value: msg.value.into(),
+        }
+    }
+}
+
+/// The protobuf type of the authz module's execute message,
+/// `cosmos.authz.v1beta1.MsgExec`
+#[derive(Clone, PartialEq, Eq, prost::Message)]
+pub struct MsgExec {
+    #[prost(string, tag = "1")]
+    pub grantee: String,
+    #[prost(message, repeated, tag = "2")]
+    pub msgs: Vec<ProtoAny>,
+}
+
+impl MsgExec {
+    pub const TYPE_URL: &'static str = "/cosmos.authz.v1beta1.MsgExec";
+}
+
+impl From<MsgExec> for AnyMsg {
+    fn from(msg: MsgExec) -> Self {
+        AnyMsg {
+            type_url: MsgExec::TYPE_URL.into(),
+            value: msg.encode_to_vec().into(),
+        }
+    }
+}
+
+/// Wrap a `MsgSend` from the granter in a `MsgExec` executed by the grantee
+fn authz_send_msg(
+    grantee: String,
+    granter: String,
+    to: String,
+    amount: Vec<ProtoCoin>,
+) -> CosmosMsg {
+    let msg_send = MsgSend {
+        from_address: granter,
+        to_address: to,
+        amount,
+    };
+    CosmosMsg::Any(
+        MsgExec {
+            grantee,
+            msgs: vec![AnyMsg::from(msg_send).into()],
+        }
+        .into(),
+    )
+}
+
+/// Convert a native coin into a protobuf coin to be sent, which the Cosmos SDK
+/// rejects if it's of zero amount
+fn sendable_coin(asset: &Asset) -> Result<ProtoCoin, AssetError> {
+    let coin = ProtoCoin::try_from(asset)?;
+    if asset.amount.is_zero() {
+        return Err(AssetError::ZeroAmount {
+            asset: asset.to_string(),
+        });
+    }
+    Ok(coin)
+}
+
+fn decode_err(err: prost::DecodeError) -> AssetError {
+    StdError::parse_err("cosmos.base.v1beta1.Coin", err).into()
+}
+
+impl TryFrom<&Asset> for ProtoCoin {
+    type Error = AssetError;
+
+    fn try_from(asset: &Asset) -> Result<Self, Self::Error> {
+        match &asset.info {
+            AssetInfo::Native(denom) => Ok(ProtoCoin {
+                denom: denom.clone(),
+                amount: asset.amount.to_string(),
+            }),
+            AssetInfo::Cw20(_) => Err(AssetError::CannotCastToStdCoin {
+                asset: asset.to_string(),
+            }),
+        }
+    }
+}
+
+impl From<Coin> for ProtoCoin {
+    fn from(coin: Coin) -> Self {
+        ProtoCoin {
+            denom: coin.denom,
+            amount: coin.amount.to_string(),
+        }
+    }
+}
+
+impl TryFrom<ProtoCoin> for Asset {
+    type Error = AssetError;
+
+    fn try_from(coin: ProtoCoin) -> Result<Self, Self::Error> {
+        let amount: Uint128 = coin.amount.parse().map_err(|_| AssetError::InvalidAssetAmount {
+            amount: coin.amount.clone(),
+        })?;
+        Ok(Asset::native(coin.denom, amount))
+    }
+}
+
+impl Asset {
+    /// Encode a native coin as a protobuf `cosmos.base.v1beta1.Coin`
+    ///
+    /// NOTE: Only works for native coins. Returns error if invoked on an
+    /// `Asset` instance representing a CW20 token.
+    ///
+    /// ```rust
+    /// use cw_asset::Asset;
+    ///
+    /// let asset = Asset::native("uatom", 12345u128);
+    /// let bytes = asset.to_proto_bytes().unwrap();
+    ///
+    /// assert_eq!(Asset::from_proto_bytes(&bytes).unwrap(), asset);
+    /// ```
+    pub fn to_proto_bytes(&self) -> Result<Binary, AssetError> {
+        ProtoCoin::try_from(self).map(|coin| coin.encode_to_vec().into())
+    }
+
+    /// Decode a protobuf `cosmos.base.v1beta1.Coin` into an **asset**
+    pub fn from_proto_bytes(bytes: &[u8]) -> Result<Self, AssetError> {
+        ProtoCoin::decode(bytes).map_err(decode_err)?.try_into()
+    }
+
+    /// Generate a protobuf-encoded `MsgSend` message that sends a native coin
+    /// from `from` to `to`, as an alternative to `BankMsg::Send`
+    ///
+    /// `from` must be the address of the contract dispatching the message.
+    ///
+    /// NOTE: Only works for native coins. Returns error if invoked on an
+    /// `Asset` instance representing a CW20 token, or an
+    /// `AssetError::ZeroAmount` error if the amount is zero.
+    pub fn msg_send<A: Into<String>, B: Into<String>>(
+        &self,
+        from: A,
+        to: B,
+    ) -> Result<CosmosMsg, AssetError> {
+        Ok(CosmosMsg::Any(
+            MsgSend {
+                from_address: from.into(),
+                to_address: to.into(),
+                amount: vec![sendable_coin(self)?],
+            }
+            .into(),
+        ))
+    }
+
+    /// Generate a message that transfers the asset from `granter` to `to`, on
+    /// behalf of `granter`
+    ///
+    /// `grantee` must be the address of the contract dispatching the message.
+    ///
+    /// - For native coins, generates an authz `MsgExec` executed by `grantee`,
+    ///   wrapping a `MsgSend` from `granter`. `granter` must have granted
+    ///   `grantee` a send authorization. Returns an `AssetError::ZeroAmount`
+    ///   error if the amount is zero.
+    /// - For CW20 tokens, falls back to [`Asset::transfer_from_msg`].
+    ///   `granter` must have given `grantee` an allowance.
+    ///
+    /// ```rust
+    /// use cosmwasm_std::{Addr, Env, Response};
+    /// use cw_asset::{Asset, AssetError};
+    ///
+    /// fn pull_asset(env: &Env, asset: &Asset, user_addr: &Addr) -> Result<Response, AssetError> {
+    ///     let contract_addr = &env.contract.address;
+    ///     let msg = asset.transfer_on_behalf_msg(contract_addr, user_addr, contract_addr)?;
+    ///
+    ///     Ok(Response::new().add_message(msg).add_attribute("asset_pulled", asset.to_string()))
+    /// }
+    /// ```
+    pub fn transfer_on_behalf_msg<A: Into<String>, B: Into<String>, C: Into<String>>(
+        &self,
+        grantee: A,
+        granter: B,
+        to: C,
+    ) -> Result<CosmosMsg, AssetError> {
+        match &self.info {
+            AssetInfo::Native(_) => Ok(authz_send_msg(
+                grantee.into(),
+                granter.into(),
+                to.into(),
+                vec![sendable_coin(self)?],
+            )),
+            AssetInfo::Cw20(_) => self.transfer_from_msg(granter, to),
+        }
+    }
+}
+
+impl AssetList {
+    /// Encode the list as a protobuf message with a single field,
+    /// `repeated cosmos.base.v1beta1.Coin balances = 1`
+    ///
+    /// The coins are sorted and merged as described in
+    /// [`AssetList::to_proto_coins`].
+    ///
+    /// NOTE: Only works if every asset in the list is a native coin. Returns
+    /// error if the list contains any CW20 token.
+    ///
+    /// ```rust
+    /// use cw_asset::{Asset, AssetList};
+    ///
+    /// let list =
+    ///     AssetList::from(vec![Asset::native("uatom", 100u128), Asset::native("uosmo", 200u128)]);
+    /// let bytes = list.to_proto_bytes().unwrap();
+    ///
+    /// assert_eq!(AssetList::from_proto_bytes(&bytes).unwrap(), list);
+    /// ```
+    pub fn to_proto_bytes(&self) -> Result<Binary, AssetError> {
+        let coins = ProtoCoins {
+            balances: self.to_proto_coins()?,
+        };
+        Ok(coins.encode_to_vec().into())
+    }
+
+    /// Decode a protobuf message whose field 1 is
+    /// `repeated cosmos.base.v1beta1.Coin`, such as the bank module's
+    /// `QueryAllBalancesResponse`, into an **asset list**
+    ///
+    /// Other fields of the message are ignored. The coins are added to the
+    /// list using [`AssetList::add`], so duplicate denoms are merged.
+    pub fn from_proto_bytes(bytes: &[u8]) -> Result<Self, AssetError> {
+        let coins = ProtoCoins::decode(bytes).map_err(decode_err)?;
+        Self::from_proto_coins(coins.balances)
+    }
+
+    /// Convert the list into protobuf coins, sorted by denom, with coins of the
+    /// same denom merged and coins of zero amount dropped, as required by the
+    /// Cosmos SDK
+    ///
+    /// NOTE: Only works if every asset in the list is a native coin. Returns
+    /// error if the list contains any CW20 token.
+    ///
+    /// ```rust
+    /// use cw_asset::{proto::ProtoCoin, Asset, AssetList};
+    ///
+    /// let list = AssetList::from(vec![
+    ///     Asset::native("uosmo", 100u128),
+    ///     Asset::native("uatom", 200u128),
+    ///     Asset::native("uusd", 0u128),
+    /// ]);
+    ///
+    /// let coins = list.to_proto_coins().unwrap();
+    /// assert_eq!(coins[0].denom, "uatom");
+    /// assert_eq!(coins[1].denom, "uosmo");
+    /// assert_eq!(coins.len(), 2);
+    /// ```
+    pub fn to_proto_coins(&self) -> Result<Vec<ProtoCoin>, AssetError> {
+        Ok(self.to_coins()?.into_iter().map(ProtoCoin::from).collect())
+    }
+
+    /// Convert protobuf coins into an **asset list**, merging duplicate denoms
+    pub fn from_proto_coins(coins: Vec<ProtoCoin>) -> Result<Self, AssetError> {
+        let mut list = AssetList::new();
+        for coin in coins {
+            list.add(&coin.try_into()?)?;
+        }
+        Ok(list)
+    }
+
+    /// Generate a single protobuf-encoded `MsgSend` message that sends all
+    /// coins in the list from `from` to `to`, as an alternative to
+    /// `BankMsg::Send`
+    ///
+    /// The coins are sorted and merged as described in
+    /// [`AssetList::to_proto_coins`]. `from` must be the address of the
+    /// contract dispatching the message.
+    ///
+    /// NOTE: Only works if every asset in the list is a native coin. Returns
+    /// error if the list contains any CW20 token.
+    pub fn msg_send<A: Into<String>, B: Into<String>>(
+        &self,
+        from: A,
+        to: B,
+    ) -> Result<CosmosMsg, AssetError> {
+        Ok(CosmosMsg::Any(
+            MsgSend {
+                from_address: from.into(),
+                to_address: to.into(),
+                amount: self.to_proto_coins()?,
+            }
+            .into(),
+        ))
+    }
+
+    /// Generate messages that transfer every asset in the list from `granter`
+    /// to `to`, on behalf of `granter`
+    ///
+    /// All native coins are sent in a single authz `MsgExec`, which comes
+    /// first, sorted and merged as described in [`AssetList::split_natives`];
+    /// each CW20 token is drawn with a `TransferFrom` message. See
+    /// [`Asset::transfer_on_behalf_msg`] for details.
+    pub fn transfer_on_behalf_msgs<
+        A: Into<String>,
+        B: Into<String> + Clone,
+        C: Into<String> + Clone,
+    >(
+        &self,
+        grantee: A,
+        granter: B,
+        to: C,
+    ) -> Result<Vec<CosmosMsg>, AssetError> {
+        let (coins, cw20s) = self.split_natives()?;
+
+        let mut msgs = vec![];
+
+        if !coins.is_empty() {
+            msgs.push(authz_send_msg(
+                grantee.into(),
+                granter.clone().into(),
+                to.clone().into(),
+                coins.into_iter().map(ProtoCoin::from).collect(),
+            ));
+        }
+
+        for asset in &cw20s {
+            msgs.push(asset.transfer_from_msg(granter.clone(), to.clone())?);
+        }
+
+        Ok(msgs)
+    }
+}
+
+//------------------------------------------------------------------------------
+// Tests
+//------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::Addr;
+
+    use super::*;
+
+    #[test]
+    fn encoding_coin() {
+        let asset = Asset::native("uatom", 12345u128);
+        let bytes = asset.to_proto_bytes().unwrap();
+
+        // field 1 (denom), length 5; field 2 (amount), length 5
+        assert_eq!(bytes.as_slice(), b"\x0a\x05uatom\x12\x0512345");
+        assert_eq!(Asset::from_proto_bytes(&bytes).unwrap(), asset);
+
+        let err = Asset::cw20(Addr::unchecked("mock_token"), 1u128).to_proto_bytes();
+        assert_eq!(
+            err,
+            Err(AssetError::CannotCastToStdCoin {
+                asset: "cw20:mock_token:1".into(),
+            }),
+        );
+    }
+
+    #[test]
+    fn decoding_invalid_coin() {
+        let bytes = ProtoCoin {
+            denom: "uatom".into(),
+            amount: "-1".into(),
+        }
+        .encode_to_vec();
+        assert_eq!(
+            Asset::from_proto_bytes(&bytes),
+            Err(AssetError::InvalidAssetAmount {
+                amount: "-1".into(),
+            }),
+        );
+
+        assert!(matches!(Asset::from_proto_bytes(b"\x0a\x05uat"), Err(AssetError::Std(_))));
+    }
+
+    #[test]
+    fn encoding_list() {
+        let list =
+            AssetList::from(vec![Asset::native("uatom", 100u128), Asset::native("uosmo", 200u128)]);
+        let bytes = list.to_proto_bytes().unwrap();
+        assert_eq!(AssetList::from_proto_bytes(&bytes).unwrap(), list);
+        assert_eq!(AssetList::from_proto_bytes(&[]).unwrap(), AssetList::new());
+
+        // field 1 (balances), length 12, containing an encoded coin
+        let list = AssetList::from(vec![Asset::native("uatom", 100u128)]);
+        let bytes = list.to_proto_bytes().unwrap();
+        assert_eq!(bytes.as_slice(), b"\x0a\x0c\x0a\x05uatom\x12\x03100");
+
+        // other fields, e.g. the pagination of `QueryAllBalancesResponse`, are ignored
+        let mut response = bytes.to_vec();
+        response.extend(b"\x12\x02\x0a\x00");
+        assert_eq!(AssetList::from_proto_bytes(&response).unwrap(), list);
+
+        assert!(matches!(
+            AssetList::from_proto_bytes(b"\x0a\x0c\x0a\x05uat"),
+            Err(AssetError::Std(_)),
+        ));
+
+        let coins = vec![
+            ProtoCoin {
+                denom: "uatom".into(),
+                amount: "100".into(),
+            },
+            ProtoCoin {
+                denom: "uatom".into(),
+                amount: "50".into(),
+            },
+        ];
+        assert_eq!(
+            AssetList::from_proto_coins(coins).unwrap(),
+            AssetList::from(vec![Asset::native("uatom", 150u128)]),
+        );
+
+        let mut list = list;
+        list.add(&Asset::cw20(Addr::unchecked("mock_token"), 1u128)).unwrap();
+        assert!(list.to_proto_bytes().is_err());
+    }
+
+    #[test]
+    fn building_msg_send() {
+        let list =
+            AssetList::from(vec![Asset::native("uatom", 100u128), Asset::native("uosmo", 200u128)]);
+
+        let msg = list.msg_send("contract", "alice").unwrap();
+        let CosmosMsg::Any(any) = msg else {
+            panic!("expected an any message");
+        };
+        assert_eq!(any.type_url, "/cosmos.bank.v1beta1.MsgSend");
+        assert_eq!(
+            MsgSend::decode(any.value.as_slice()).unwrap(),
+            MsgSend {
+                from_address: "contract".into(),
+                to_address: "alice".into(),
+                amount: list.to_proto_coins().unwrap(),
+            },
+        );
+
+        // coins are sorted by denom, merged, and zero amounts dropped
+        let list = AssetList::from(vec![
+            Asset::native("uosmo", 1u128),
+            Asset::native("uatom", 2u128),
+            Asset::native("uatom", 0u128),
+        ]);
+        let expected = vec![
+            ProtoCoin {
+                denom: "uatom".into(),
+                amount: "2".into(),
+            },
+            ProtoCoin {
+                denom: "uosmo".into(),
+                amount: "1".into(),
+            },
+        ];
+        assert_eq!(list.to_proto_coins().unwrap(), expected);
+
+        let CosmosMsg::Any(any) = list.msg_send("contract", "alice").unwrap() else {
+            panic!("expected an any message");
+        };
+        assert_eq!(MsgSend::decode(any.value.as_slice()).unwrap().amount, expected);
+
+        let msg = list[0].msg_send("contract", "alice").unwrap();
+        let CosmosMsg::Any(any) = msg else {
+            panic!("expected an any message");
+        };
+        assert_eq!(MsgSend::decode(any.value.as_slice()).unwrap().amount.len(), 1);
+
+        let err = Asset::cw20(Addr::unchecked("mock_token"), 1u128).msg_send("contract", "alice");
+        assert!(err.is_err());
+
+        let err = Asset::native("uatom", 0u128).msg_send("contract", "alice");
+        assert_eq!(
+            err,
+            Err(AssetError::ZeroAmount {
+                asset: "native:uatom:0".into(),
+            }),
+        );
+    }
+
+    fn decode_authz_send(msg: &CosmosMsg) -> (String, MsgSend) {
+        let CosmosMsg::Any(any) = msg else {
+            panic!("expected an any message");
+        };
+        assert_eq!(any.type_url, MsgExec::TYPE_URL);
+
+        let mut msg_exec = MsgExec::decode(any.value.as_slice()).unwrap();
+        assert_eq!(msg_exec.msgs.len(), 1);
+
+        let inner = msg_exec.msgs.remove(0);
+        assert_eq!(inner.type_url, MsgSend::TYPE_URL);
+
+        (msg_exec.grantee, MsgSend::decode(inner.value.as_slice()).unwrap())
+    }
+
+    #[test]
+    fn transferring_on_behalf() {
+        let msg = Asset::native("uatom", 100u128)
+            .transfer_on_behalf_msg("contract", "granter", "alice")
+            .unwrap();
+        let (grantee, msg_send) = decode_authz_send(&msg);
+        assert_eq!(grantee, "contract");
+        assert_eq!(
+            msg_send,
+            MsgSend {
+                from_address: "granter".into(),
+                to_address: "alice".into(),
+                amount: vec![ProtoCoin {
+                    denom: "uatom".into(),
+                    amount: "100".into(),
+                }],
+            },
+        );
+
+        let err =
+            Asset::native("uatom", 0u128).transfer_on_behalf_msg("contract", "granter", "alice");
+        assert_eq!(
+            err,
+            Err(AssetError::ZeroAmount {
+                asset: "native:uatom:0".into(),
+            }),
+        );
+
+        let asset = Asset::cw20(Addr::unchecked("mock_token"), 100u128);
+        let msg = asset.transfer_on_behalf_msg("contract", "granter", "alice").unwrap();
+        assert_eq!(msg, asset.transfer_from_msg("granter", "alice").unwrap());
+    }
+
+    #[test]
+    fn transferring_list_on_behalf() {
+        let list = AssetList::from(vec![
+            Asset::native("uatom", 100u128),
+            Asset::cw20(Addr::unchecked("mock_token"), 200u128),
+            Asset::native("uosmo", 300u128),
+        ]);
+
+        let msgs = list.transfer_on_behalf_msgs("contract", "granter", "alice").unwrap();
+        assert_eq!(msgs.len(), 2);
+
+        let (grantee, msg_send) = decode_authz_send(&msgs[0]);
+        assert_eq!(grantee, "contract");
+        assert_eq!(msg_send.from_address, "granter");
+        assert_eq!(
+            AssetList::from_proto_coins(msg_send.amount).unwrap(),
+            AssetList::from(vec![Asset::native("uatom", 100u128), Asset::native("uosmo", 300u128)]),
+        );
+
+        assert_eq!(msgs[1], list[1].transfer_from_msg("granter", "alice").unwrap());
+
+        let list = AssetList::from(vec![
+            Asset::native("uosmo", 1u128),
+            Asset::native("uatom", 2u128),
+            Asset::native("uatom", 0u128),
+            Asset::native("uusd", 0u128),
+        ]);
+        let msgs = list.transfer_on_behalf_msgs("contract", "granter", "alice").unwrap();
+        assert_eq!(msgs.len(), 1);
+        let (_, msg_send) = decode_authz_send(&msgs[0]);
+        assert_eq!(msg_send.amount, list.to_proto_coins().unwrap());
+        assert_eq!(msg_send.amount[0].denom, "uatom");
+
+        let list = AssetList::from(vec![Asset::native("uusd", 0u128)]);
+        assert!(list.transfer_on_behalf_msgs("contract", "granter", "alice").unwrap().is_empty());
+
+        let list = AssetList::from(vec![Asset::cw20(Addr::unchecked("mock_token"), 200u128)]);
+        let msgs = list.transfer_on_behalf_msgs("contract", "granter", "alice").unwrap();
+        assert_eq!(msgs, vec![list[0].transfer_from_msg("granter", "alice").unwrap()]);
+    }
+}
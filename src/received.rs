@@ -0,0 +1,334 @@
+use cosmwasm_std::{Addr, Attribute, Event, Reply, StdError};
+
+use crate::{asset::parse_sdk_coin, Asset, AssetError, AssetList};
+
+// events emitted by the bank module
+const COIN_RECEIVED_EVENT: &str = "coin_received";
+const COIN_SPENT_EVENT: &str = "coin_spent";
+const TRANSFER_EVENT: &str = "transfer";
+
+// event emitted by the wasm module, containing the attributes of contracts
+const WASM_EVENT: &str = "wasm";
+const CONTRACT_ADDRESS_KEY: &str = "_contract_address";
+
+fn attr<'a>(attributes: &'a [Attribute], key: &str) -> Option<&'a str> {
+    attributes.iter().find(|attr| attr.key == key).map(|attr| attr.value.as_str())
+}
+
+/// Parse a comma-separated list of SDK coins, as found in bank events
+fn parse_coins(s: &str) -> Result<Vec<Asset>, AssetError> {
+    if s.is_empty() {
+        return Ok(vec![]);
+    }
+
+    s.split(',')
+        .map(|coin_str| {
+            let (denom, amount) = parse_sdk_coin(coin_str)?;
+            Ok(Asset::native(denom, amount))
+        })
+        .collect()
+}
+
+/// The bank assets moved into and out of an address, according to either the
+/// `coin_received`/`coin_spent` events, or the `transfer` events
+fn bank_movements(
+    events: &[Event],
+    address: &str,
+    incoming: &mut AssetList,
+    outgoing: &mut AssetList,
+) -> Result<(), AssetError> {
+    // the bank module emits both `coin_received` and `transfer` events for the
+    // same send, so only one kind may be counted. the former also covers
+    // minting, so it's preferred; the latter is used by environments that
+    // don't emit the former, such as cw-multi-test.
+    let has_coin_events =
+        events.iter().any(|event| event.ty == COIN_RECEIVED_EVENT || event.ty == COIN_SPENT_EVENT);
+
+    for event in events {
+        let (receiver_key, spender_key) = match event.ty.as_str() {
+            COIN_RECEIVED_EVENT if has_coin_events => (Some("receiver"), None),
+            COIN_SPENT_EVENT if has_coin_events => (None, Some("spender")),
+            TRANSFER_EVENT if !has_coin_events => (Some("recipient"), Some("sender")),
+            _ => continue,
+        };
+
+        let Some(amount) = attr(&event.attributes, "amount") else {
+            continue;
+        };
+
+        if receiver_key.and_then(|key| attr(&event.attributes, key)) == Some(address) {
+            for asset in parse_coins(amount)? {
+                incoming.add(&asset)?;
+            }
+        }
+        if spender_key.and_then(|key| attr(&event.attributes, key)) == Some(address) {
+            for asset in parse_coins(amount)? {
+                outgoing.add(&asset)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// The CW20 tokens moved into and out of an address, according to the
+/// attributes emitted by cw20-base style token contracts
+fn cw20_movements(
+    events: &[Event],
+    address: &str,
+    incoming: &mut AssetList,
+    outgoing: &mut AssetList,
+) -> Result<(), AssetError> {
+    for event in events.iter().filter(|event| event.ty == WASM_EVENT) {
+        // a single wasm event may contain the attributes of multiple contracts,
+        // each starting with the contract's address
+        let mut attributes = event.attributes.as_slice();
+        while let Some(start) = attributes.iter().position(|attr| attr.key == CONTRACT_ADDRESS_KEY)
+        {
+            let end = attributes[start + 1..]
+                .iter()
+                .position(|attr| attr.key == CONTRACT_ADDRESS_KEY)
+                .map_or(attributes.len(), |pos| start + 1 + pos);
+            let segment = &attributes[start..end];
+            attributes = &attributes[end..];
+
+            let (is_incoming, is_outgoing) = match attr(segment, "action") {
+                Some("transfer" | "send" | "transfer_from" | "send_from") => (true, true),
+                Some("mint") => (true, false),
+                Some("burn" | "burn_from") => (false, true),
+                _ => continue,
+            };
+
+            let Some(amount_str) = attr(segment, "amount") else {
+                continue;
+            };
+            let amount =
+                amount_str.parse::<u128>().map_err(|_| AssetError::InvalidAssetAmount {
+                    amount: amount_str.into(),
+                })?;
+
+            let contract_addr = Addr::unchecked(segment[0].value.clone());
+            let asset = Asset::cw20(contract_addr, amount);
+
+            if is_incoming && attr(segment, "to") == Some(address) {
+                incoming.add(&asset)?;
+            }
+            if is_outgoing && attr(segment, "from") == Some(address) {
+                outgoing.add(&asset)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+impl AssetList {
+    /// Parse the events emitted during the execution of a message, e.g. a
+    /// `SubMsg`, and return the net amounts of assets received by an address
+    ///
+    /// The following events are recognized:
+    ///
+    /// - native coins: the bank module's `coin_received` and `coin_spent`
+    ///   events, or if none are present, its `transfer` events;
+    /// - CW20 tokens: `wasm` events with the `transfer`, `send`,
+    ///   `transfer_from`, `send_from`, `mint`, `burn` or `burn_from` action,
+    ///   as emitted by the cw20-base contract.
+    ///
+    /// For each asset, the amount sent out of the address is subtracted from
+    /// the amount received; assets of which the address received no more than
+    /// it sent out are omitted.
+    ///
+    /// NOTE: Any contract can emit attributes that look like a CW20 transfer.
+    /// Only trust the returned CW20 amounts if the message can only have
+    /// executed known token contracts, or check the tokens against a whitelist.
+    pub fn received_in_events(events: &[Event], address: &str) -> Result<Self, AssetError> {
+        let mut incoming = AssetList::new();
+        let mut outgoing = AssetList::new();
+
+        bank_movements(events, address, &mut incoming, &mut outgoing)?;
+        cw20_movements(events, address, &mut incoming, &mut outgoing)?;
+
+        for sent in &outgoing {
+            incoming.apply(|asset| {
+                if asset.info == sent.info {
+                    asset.amount = asset.amount.saturating_sub(sent.amount);
+                }
+            });
+        }
+
+        Ok(incoming.purge().clone())
+    }
+
+    /// Parse the events of a `SubMsg`'s reply, and return the net amounts of
+    /// assets received by an address
+    ///
+    /// See [`AssetList::received_in_events`] for details. Returns error if the
+    /// `SubMsg` failed.
+    ///
+    /// ```rust
+    /// use cosmwasm_std::{Env, Reply, Response};
+    /// use cw_asset::{AssetError, AssetList};
+    ///
+    /// fn after_swap(env: Env, reply: Reply) -> Result<Response, AssetError> {
+    ///     let received = AssetList::received_in_reply(&reply, env.contract.address.as_str())?;
+    ///
+    ///     Ok(Response::new().add_attribute("received", received.to_string()))
+    /// }
+    /// ```
+    pub fn received_in_reply(reply: &Reply, address: &str) -> Result<Self, AssetError> {
+        let res = reply.result.clone().into_result().map_err(|err| {
+            StdError::generic_err(format!("cannot parse the events of a failed submessage: {err}"))
+        })?;
+        Self::received_in_events(&res.events, address)
+    }
+}
+
+//------------------------------------------------------------------------------
+// Tests
+//------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::{Binary, SubMsgResponse, SubMsgResult};
+
+    use super::*;
+
+    fn coin_received(receiver: &str, amount: &str) -> Event {
+        Event::new("coin_received")
+            .add_attribute("receiver", receiver)
+            .add_attribute("amount", amount)
+    }
+
+    fn coin_spent(spender: &str, amount: &str) -> Event {
+        Event::new("coin_spent").add_attribute("spender", spender).add_attribute("amount", amount)
+    }
+
+    fn transfer(sender: &str, recipient: &str, amount: &str) -> Event {
+        Event::new("transfer")
+            .add_attribute("recipient", recipient)
+            .add_attribute("sender", sender)
+            .add_attribute("amount", amount)
+    }
+
+    fn wasm_event(attributes: Vec<Attribute>) -> Event {
+        Event::new("wasm").add_attributes(attributes)
+    }
+
+    // `Attribute::new` rejects the reserved `_contract_address` key, which is
+    // only added by the chain
+    fn contract_address(contract: &str) -> Attribute {
+        Attribute {
+            key: "_contract_address".into(),
+            value: contract.into(),
+        }
+    }
+
+    fn cw20_transfer(
+        token: &str,
+        action: &str,
+        from: &str,
+        to: &str,
+        amount: u128,
+    ) -> Vec<Attribute> {
+        vec![
+            contract_address(token),
+            Attribute::new("action", action),
+            Attribute::new("from", from),
+            Attribute::new("to", to),
+            Attribute::new("amount", amount.to_string()),
+        ]
+    }
+
+    #[test]
+    fn parsing_bank_events() {
+        // a swap: the contract sends uatom to the pool, the pool sends uosmo
+        // back; the bank module emits both coin and transfer events
+        let events = vec![
+            coin_spent("contract", "100uatom"),
+            coin_received("pool", "100uatom"),
+            transfer("contract", "pool", "100uatom"),
+            coin_spent("pool", "200uosmo,5uatom"),
+            coin_received("contract", "200uosmo,5uatom"),
+            transfer("pool", "contract", "200uosmo,5uatom"),
+        ];
+        assert_eq!(
+            AssetList::received_in_events(&events, "contract").unwrap(),
+            AssetList::from(vec![Asset::native("uosmo", 200u128)]),
+        );
+        assert_eq!(
+            AssetList::received_in_events(&events, "pool").unwrap(),
+            AssetList::from(vec![Asset::native("uatom", 95u128)]),
+        );
+
+        // without coin events, e.g. in cw-multi-test
+        let events = vec![transfer("pool", "contract", "200uosmo")];
+        assert_eq!(
+            AssetList::received_in_events(&events, "contract").unwrap(),
+            AssetList::from(vec![Asset::native("uosmo", 200u128)]),
+        );
+
+        let events = vec![coin_received("contract", "abc")];
+        assert!(AssetList::received_in_events(&events, "contract").is_err());
+    }
+
+    #[test]
+    fn parsing_cw20_events() {
+        let mut attributes = cw20_transfer("token1", "transfer", "pool", "contract", 300);
+        attributes.extend(cw20_transfer("token2", "send", "contract", "pool", 400));
+        attributes.extend(cw20_transfer("token1", "transfer_from", "alice", "contract", 50));
+        attributes.extend(vec![
+            contract_address("token2"),
+            Attribute::new("action", "mint"),
+            Attribute::new("to", "contract"),
+            Attribute::new("amount", "500"),
+            contract_address("pool"),
+            Attribute::new("action", "swap"),
+            Attribute::new("amount", "12345"),
+        ]);
+
+        let events = vec![wasm_event(attributes)];
+        assert_eq!(
+            AssetList::received_in_events(&events, "contract").unwrap(),
+            AssetList::from(vec![
+                Asset::cw20(Addr::unchecked("token1"), 350u128),
+                Asset::cw20(Addr::unchecked("token2"), 100u128),
+            ]),
+        );
+        assert_eq!(
+            AssetList::received_in_events(&events, "pool").unwrap(),
+            AssetList::from(vec![Asset::cw20(Addr::unchecked("token2"), 400u128)]),
+        );
+        assert_eq!(AssetList::received_in_events(&events, "bob").unwrap(), AssetList::new());
+    }
+
+    #[test]
+    fn parsing_reply() {
+        #[allow(deprecated)]
+        let reply = Reply {
+            id: 1,
+            payload: Binary::default(),
+            gas_used: 0,
+            result: SubMsgResult::Ok(SubMsgResponse {
+                events: vec![
+                    coin_received("contract", "200uosmo"),
+                    wasm_event(cw20_transfer("token1", "transfer", "pool", "contract", 300)),
+                ],
+                data: None,
+                msg_responses: vec![],
+            }),
+        };
+        assert_eq!(
+            AssetList::received_in_reply(&reply, "contract").unwrap(),
+            AssetList::from(vec![
+                Asset::native("uosmo", 200u128),
+                Asset::cw20(Addr::unchecked("token1"), 300u128),
+            ]),
+        );
+
+        let reply = Reply {
+            result: SubMsgResult::Err("swap failed".into()),
+            ..reply
+        };
+        assert!(AssetList::received_in_reply(&reply, "contract").is_err());
+    }
+}
@@ -0,0 +1,226 @@
+//! JSON schemas of the unchecked asset types, for frontends and code generators
+//! such as [ts-codegen](https://github.com/CosmWasm/ts-codegen)
+//!
+//! The schemas derived for [`AssetInfoBase`](crate::AssetInfoBase),
+//! [`AssetBase`](crate::AssetBase) and [`AssetListBase`](crate::AssetListBase)
+//! are named after the generic address type, e.g. `AssetInfoBase_for_String`.
+//! The schemas returned by this module are instead named after the unchecked
+//! type aliases, and include example values and the string forms accepted by
+//! the types' `FromStr` implementations.
+//!
+//! The exported schemas are checked in under the crate's `schema` directory.
+//! The test suite fails if they differ from the generated schemas; run it with
+//! the `UPDATE_SCHEMAS` environment variable set to regenerate them.
+//!
+//! ```rust
+//! use cw_asset::schema::asset_info_unchecked_schema;
+//!
+//! let schema = asset_info_unchecked_schema();
+//! assert_eq!(schema.schema.metadata.unwrap().title.unwrap(), "AssetInfoUnchecked");
+//! assert!(schema.definitions.is_empty());
+//! ```
+
+use std::path::Path;
+
+use cosmwasm_schema::{
+    export_schema, schema_for,
+    schemars::schema::{RootSchema, Schema, SchemaObject},
+    serde::Serialize,
+};
+use cosmwasm_std::{from_json, to_json_string, to_json_vec, Addr};
+
+use crate::{Asset, AssetInfo, AssetInfoUnchecked, AssetList, AssetListUnchecked, AssetUnchecked};
+
+/// The schema names derived for the generic types, and the names they are
+/// replaced with
+const RENAMES: &[(&str, &str)] = &[
+    ("AssetInfoBase_for_String", "AssetInfoUnchecked"),
+    ("AssetBase_for_String", "AssetUnchecked"),
+    ("AssetListBase_for_String", "AssetListUnchecked"),
+];
+
+/// The address of the CW20 token used in example values
+const EXAMPLE_TOKEN: &str = "cosmwasm183rfa8tvtp6ax7jr7dfaf7ywv870sykxdmhrg46xtfqtpkjp20sqhqg7re";
+
+fn example_infos() -> Vec<AssetInfoUnchecked> {
+    vec![AssetInfoUnchecked::native("uatom"), AssetInfoUnchecked::cw20(EXAMPLE_TOKEN)]
+}
+
+fn example_assets() -> Vec<AssetUnchecked> {
+    vec![AssetUnchecked::native("uatom", 12345u128), AssetUnchecked::cw20(EXAMPLE_TOKEN, 67890u128)]
+}
+
+fn example_lists() -> Vec<AssetListUnchecked> {
+    let list = AssetList::from(vec![
+        Asset::native("uatom", 12345u128),
+        Asset::new(AssetInfo::cw20(Addr::unchecked(EXAMPLE_TOKEN)), 67890u128),
+    ]);
+    vec![list.into()]
+}
+
+/// Describe the string form of a type, and set the example values
+fn annotate<T: Serialize>(schema: &mut SchemaObject, string_form: &str, examples: &[T]) {
+    let metadata = schema.metadata();
+    let description = metadata.description.take().unwrap_or_default();
+    metadata.description = Some(format!("{description}\n\n{string_form}").trim().into());
+    metadata.examples =
+        examples.iter().map(|example| from_json(to_json_vec(example).unwrap()).unwrap()).collect();
+}
+
+fn annotate_by_name(name: &str, schema: &mut SchemaObject) {
+    match name {
+        "AssetInfoUnchecked" => annotate(
+            schema,
+            "In string form: `native:{denom}` or `cw20:{contract_addr}`.",
+            &example_infos(),
+        ),
+        "AssetUnchecked" => annotate(
+            schema,
+            "In string form: `native:{denom}:{amount}` or `cw20:{contract_addr}:{amount}`.",
+            &example_assets(),
+        ),
+        "AssetListUnchecked" => annotate(
+            schema,
+            "In string form: the string forms of the assets, separated by commas, e.g. \
+             `native:uatom:12345,cw20:{contract_addr}:67890`.",
+            &example_lists(),
+        ),
+        _ => (),
+    }
+}
+
+/// Rename the generic types in a derived schema, and annotate them
+fn stabilize(schema: RootSchema) -> RootSchema {
+    let mut json = to_json_string(&schema).unwrap();
+    for (from, to) in RENAMES {
+        json = json.replace(&format!("\"{from}\""), &format!("\"{to}\""));
+        json = json.replace(&format!("/{from}\""), &format!("/{to}\""));
+    }
+    let mut schema: RootSchema = from_json(json).unwrap();
+
+    let title = schema.schema.metadata().title.clone().unwrap_or_default();
+    annotate_by_name(&title, &mut schema.schema);
+    for (name, definition) in &mut schema.definitions {
+        if let Schema::Object(object) = definition {
+            annotate_by_name(name, object);
+        }
+    }
+
+    schema
+}
+
+/// Return the JSON schema of [`AssetInfoUnchecked`]
+pub fn asset_info_unchecked_schema() -> RootSchema {
+    stabilize(schema_for!(AssetInfoUnchecked))
+}
+
+/// Return the JSON schema of [`AssetUnchecked`]
+pub fn asset_unchecked_schema() -> RootSchema {
+    stabilize(schema_for!(AssetUnchecked))
+}
+
+/// Return the JSON schema of [`AssetListUnchecked`]
+pub fn asset_list_unchecked_schema() -> RootSchema {
+    stabilize(schema_for!(AssetListUnchecked))
+}
+
+/// Write the JSON schemas of the unchecked types to the given directory, one
+/// file per type, e.g. `asset_info_unchecked.json`
+///
+/// Panics if a file cannot be written.
+pub fn export_schemas(out_dir: &Path) {
+    export_schema(&asset_info_unchecked_schema(), out_dir);
+    export_schema(&asset_unchecked_schema(), out_dir);
+    export_schema(&asset_list_unchecked_schema(), out_dir);
+}
+
+//------------------------------------------------------------------------------
+// Tests
+//------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, path::PathBuf};
+
+    use cosmwasm_std::testing::MockApi;
+
+    use super::*;
+
+    fn definition_names(schema: &RootSchema) -> Vec<&str> {
+        schema.definitions.keys().map(String::as_str).collect()
+    }
+
+    #[test]
+    fn naming_schemas() {
+        let schema = asset_info_unchecked_schema();
+        assert_eq!(
+            schema.schema.metadata.as_ref().unwrap().title.as_deref(),
+            Some("AssetInfoUnchecked"),
+        );
+        assert!(definition_names(&schema).is_empty());
+
+        let schema = asset_unchecked_schema();
+        assert_eq!(
+            schema.schema.metadata.as_ref().unwrap().title.as_deref(),
+            Some("AssetUnchecked"),
+        );
+        assert_eq!(definition_names(&schema), vec!["AssetInfoUnchecked", "Uint128"]);
+
+        let schema = asset_list_unchecked_schema();
+        assert_eq!(
+            schema.schema.metadata.as_ref().unwrap().title.as_deref(),
+            Some("AssetListUnchecked"),
+        );
+        assert_eq!(
+            definition_names(&schema),
+            vec!["AssetInfoUnchecked", "AssetUnchecked", "Uint128"],
+        );
+
+        let json = to_json_string(&schema).unwrap();
+        assert!(!json.contains("_for_String"));
+    }
+
+    #[test]
+    fn examples_are_valid() {
+        let api = MockApi::default();
+        assert_eq!(api.addr_make("token").as_str(), EXAMPLE_TOKEN);
+
+        let schema = asset_list_unchecked_schema();
+        for example in &schema.schema.metadata.as_ref().unwrap().examples {
+            let list: AssetListUnchecked = from_json(to_json_vec(example).unwrap()).unwrap();
+            list.check(&api, None).unwrap();
+        }
+
+        let Schema::Object(info_schema) = &schema.definitions["AssetInfoUnchecked"] else {
+            panic!("expected a schema object");
+        };
+        let metadata = info_schema.metadata.as_ref().unwrap();
+        assert!(metadata.description.as_ref().unwrap().contains("`native:{denom}`"));
+        for example in &metadata.examples {
+            let info: AssetInfoUnchecked = from_json(to_json_vec(example).unwrap()).unwrap();
+            info.check(&api, None).unwrap();
+        }
+    }
+
+    #[test]
+    fn checked_in_schemas_are_up_to_date() {
+        let out_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("schema");
+        if env::var_os("UPDATE_SCHEMAS").is_some() {
+            fs::create_dir_all(&out_dir).unwrap();
+            export_schemas(&out_dir);
+        }
+
+        for (file, schema) in [
+            ("asset_info_unchecked.json", asset_info_unchecked_schema()),
+            ("asset_unchecked.json", asset_unchecked_schema()),
+            ("asset_list_unchecked.json", asset_list_unchecked_schema()),
+        ] {
+            let json = fs::read_to_string(out_dir.join(file)).unwrap();
+            let checked_in: RootSchema = from_json(json).unwrap();
+            assert!(
+                checked_in == schema,
+                "schema/{file} is out of date; rerun the tests with UPDATE_SCHEMAS=1",
+            );
+        }
+    }
+}
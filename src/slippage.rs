@@ -0,0 +1,271 @@
+use std::fmt;
+
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Decimal, StdError, Uint128};
+
+use crate::{conversion::mul_fraction, Asset, AssetError, AssetInfo, AssetList, Rounding};
+
+/// An **asset** of which less than the minimum acceptable amount was received,
+/// as reported by `AssetError::MaxSlippageExceeded`
+#[cw_serde]
+pub struct SlippageViolation {
+    /// The asset that was received
+    pub info: AssetInfo,
+    /// The amount that was expected to be received
+    pub expected: Uint128,
+    /// The amount that was actually received
+    pub actual: Uint128,
+    /// The maximum slippage that was tolerated
+    pub max_slippage: Decimal,
+}
+
+impl fmt::Display for SlippageViolation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}: expected {}, received {}, max slippage {}",
+            self.info, self.expected, self.actual, self.max_slippage
+        )
+    }
+}
+
+/// Return the minimum amount acceptable when expecting `expected` with the
+/// given max slippage, rounded up
+fn min_amount(expected: Uint128, max_slippage: Decimal) -> Result<Uint128, AssetError> {
+    let tolerance = Decimal::one().checked_sub(max_slippage).map_err(|_| {
+        StdError::generic_err(format!("max slippage must be between 0 and 1, got {max_slippage}"))
+    })?;
+    mul_fraction(expected, tolerance, Rounding::Ceil)
+}
+
+impl Asset {
+    /// Return the minimum amount of the **asset** acceptable when expecting to
+    /// receive it, given the max slippage, e.g. `Decimal::percent(1)` for 1%
+    ///
+    /// The minimum amount is rounded up. Returns error if the max slippage is
+    /// greater than 1.
+    ///
+    /// ```rust
+    /// use cosmwasm_std::Decimal;
+    /// use cw_asset::Asset;
+    ///
+    /// let expected = Asset::native("uosmo", 12345u128);
+    /// let minimum = expected.min_receive(Decimal::percent(1)).unwrap();
+    /// assert_eq!(minimum, Asset::native("uosmo", 12222u128));
+    /// ```
+    pub fn min_receive(&self, max_slippage: Decimal) -> Result<Asset, AssetError> {
+        Ok(Asset::new(self.info.clone(), min_amount(self.amount, max_slippage)?))
+    }
+
+    /// Assert that the **asset**, e.g. the output of a swap, is no less than the
+    /// expected **asset** reduced by the max slippage
+    ///
+    /// Returns an `AssetError::MaxSlippageExceeded` error if the assertion
+    /// fails, or error if the assets are of different types.
+    ///
+    /// ```rust
+    /// use cosmwasm_std::Decimal;
+    /// use cw_asset::Asset;
+    ///
+    /// let expected = Asset::native("uosmo", 12345u128);
+    ///
+    /// let actual = Asset::native("uosmo", 12222u128);
+    /// assert!(actual.assert_slippage(&expected, Decimal::percent(1)).is_ok());
+    ///
+    /// let actual = Asset::native("uosmo", 12221u128);
+    /// assert!(actual.assert_slippage(&expected, Decimal::percent(1)).is_err());
+    /// ```
+    pub fn assert_slippage(
+        &self,
+        expected: &Asset,
+        max_slippage: Decimal,
+    ) -> Result<(), AssetError> {
+        if self.info != expected.info {
+            return Err(StdError::generic_err(format!(
+                "cannot compare asset {} against expected asset {}",
+                self.info, expected.info
+            ))
+            .into());
+        }
+
+        if self.amount < min_amount(expected.amount, max_slippage)? {
+            return Err(AssetError::MaxSlippageExceeded {
+                violations: vec![SlippageViolation {
+                    info: self.info.clone(),
+                    expected: expected.amount,
+                    actual: self.amount,
+                    max_slippage,
+                }],
+            });
+        }
+
+        Ok(())
+    }
+}
+
+impl AssetList {
+    /// Return the minimum amount of each **asset** in the **asset list**
+    /// acceptable when expecting to receive it, given the max slippage
+    ///
+    /// See [`Asset::min_receive`] for details.
+    pub fn min_receive(&self, max_slippage: Decimal) -> Result<AssetList, AssetError> {
+        self.into_iter()
+            .map(|asset| asset.min_receive(max_slippage))
+            .collect::<Result<Vec<_>, _>>()
+            .map(AssetList::from)
+    }
+
+    /// Assert that the **asset list**, e.g. the outputs of a multi-hop swap,
+    /// contains no less than each **asset** in the expected **asset list**
+    /// reduced by the max slippage
+    ///
+    /// Assets missing from the list are considered received with zero amount;
+    /// assets not in the expected list are ignored. Returns an
+    /// `AssetError::MaxSlippageExceeded` error reporting every violating asset
+    /// if the assertion fails.
+    ///
+    /// ```rust
+    /// use cosmwasm_std::Decimal;
+    /// use cw_asset::{Asset, AssetList};
+    ///
+    /// let expected =
+    ///     AssetList::from(vec![Asset::native("uosmo", 12345u128), Asset::native("uatom", 100u128)]);
+    ///
+    /// let actual =
+    ///     AssetList::from(vec![Asset::native("uatom", 99u128), Asset::native("uosmo", 12300u128)]);
+    /// assert!(actual.assert_slippage(&expected, Decimal::percent(1)).is_ok());
+    ///
+    /// let actual = AssetList::from(vec![Asset::native("uosmo", 12300u128)]);
+    /// assert!(actual.assert_slippage(&expected, Decimal::percent(1)).is_err());
+    /// ```
+    pub fn assert_slippage(
+        &self,
+        expected: &AssetList,
+        max_slippage: Decimal,
+    ) -> Result<(), AssetError> {
+        let mut violations = vec![];
+        for asset in expected {
+            let actual = self.find(&asset.info).map(|a| a.amount).unwrap_or_default();
+            if actual < min_amount(asset.amount, max_slippage)? {
+                violations.push(SlippageViolation {
+                    info: asset.info.clone(),
+                    expected: asset.amount,
+                    actual,
+                    max_slippage,
+                });
+            }
+        }
+
+        if !violations.is_empty() {
+            return Err(AssetError::MaxSlippageExceeded {
+                violations,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+//------------------------------------------------------------------------------
+// Tests
+//------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::mock_token;
+
+    #[test]
+    fn computing_min_receive() {
+        let expected = Asset::native("uosmo", 12345u128);
+        assert_eq!(expected.min_receive(Decimal::zero()).unwrap(), expected);
+        assert_eq!(
+            expected.min_receive(Decimal::percent(1)).unwrap(),
+            Asset::native("uosmo", 12222u128),
+        );
+        assert_eq!(expected.min_receive(Decimal::one()).unwrap(), Asset::native("uosmo", 0u128));
+        assert!(expected.min_receive(Decimal::percent(101)).is_err());
+
+        let list = AssetList::from(vec![expected, Asset::new(mock_token(), 1000u128)]);
+        assert_eq!(
+            list.min_receive(Decimal::percent(5)).unwrap(),
+            AssetList::from(vec![
+                Asset::native("uosmo", 11728u128),
+                Asset::new(mock_token(), 950u128),
+            ]),
+        );
+    }
+
+    #[test]
+    fn asserting_asset_slippage() {
+        let expected = Asset::new(mock_token(), 1000u128);
+        let max_slippage = Decimal::percent(5);
+
+        assert!(Asset::new(mock_token(), 950u128).assert_slippage(&expected, max_slippage).is_ok());
+        assert!(Asset::new(mock_token(), 2000u128)
+            .assert_slippage(&expected, max_slippage)
+            .is_ok());
+
+        let err = Asset::new(mock_token(), 949u128).assert_slippage(&expected, max_slippage);
+        assert_eq!(
+            err,
+            Err(AssetError::MaxSlippageExceeded {
+                violations: vec![SlippageViolation {
+                    info: mock_token(),
+                    expected: Uint128::new(1000),
+                    actual: Uint128::new(949),
+                    max_slippage,
+                }],
+            }),
+        );
+        assert_eq!(
+            err.unwrap_err().to_string(),
+            "max slippage exceeded: cw20:mock_token: expected 1000, received 949, max slippage 0.05",
+        );
+
+        let err = Asset::native("uosmo", 1000u128).assert_slippage(&expected, max_slippage);
+        assert!(matches!(err, Err(AssetError::Std(_))));
+    }
+
+    #[test]
+    fn asserting_list_slippage() {
+        let expected = AssetList::from(vec![
+            Asset::native("uosmo", 12345u128),
+            Asset::native("uatom", 100u128),
+            Asset::new(mock_token(), 1000u128),
+        ]);
+        let max_slippage = Decimal::percent(1);
+
+        let actual = AssetList::from(vec![
+            Asset::new(mock_token(), 990u128),
+            Asset::native("uusd", 1u128),
+            Asset::native("uatom", 99u128),
+            Asset::native("uosmo", 12300u128),
+        ]);
+        assert!(actual.assert_slippage(&expected, max_slippage).is_ok());
+
+        let actual = AssetList::from(vec![
+            Asset::new(mock_token(), 989u128),
+            Asset::native("uosmo", 12300u128),
+        ]);
+        assert_eq!(
+            actual.assert_slippage(&expected, max_slippage),
+            Err(AssetError::MaxSlippageExceeded {
+                violations: vec![
+                    SlippageViolation {
+                        info: AssetInfo::native("uatom"),
+                        expected: Uint128::new(100),
+                        actual: Uint128::zero(),
+                        max_slippage,
+                    },
+                    SlippageViolation {
+                        info: mock_token(),
+                        expected: Uint128::new(1000),
+                        actual: Uint128::new(989),
+                        max_slippage,
+                    },
+                ],
+            }),
+        );
+    }
+}
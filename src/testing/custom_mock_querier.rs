@@ -1,14 +1,42 @@
+use std::collections::BTreeMap;
+
 use cosmwasm_std::{
-    from_json, testing::MockQuerier, Addr, Coin, Empty, Querier, QuerierResult, QueryRequest,
-    StdResult, SystemError, WasmQuery,
+    from_json,
+    testing::{mock_env, MockQuerier},
+    Addr, BlockInfo, Coin, DenomMetadata, Empty, Querier, QuerierResult, QueryRequest, StdResult,
+    SystemError, WasmQuery,
 };
-use cw20::Cw20QueryMsg;
+use cw20::{Cw20QueryMsg, EmbeddedLogo, Expiration, MarketingInfoResponse};
 
 use super::cw20_querier::Cw20Querier;
 
+/// A mock querier that answers bank queries using cosmwasm-std's `MockQuerier`,
+/// and CW20 queries using [`Cw20Querier`]
+///
+/// Mock data can either be set on an existing instance using the `set_*`
+/// methods, or seeded when building a new instance using the `with_*` methods:
+///
+/// ```rust
+/// use cosmwasm_std::Coin;
+/// use cw20::Expiration;
+/// use cw_asset::testing::{mock_dependencies_with_querier, CustomMockQuerier};
+///
+/// let querier = CustomMockQuerier::default()
+///     .with_base_balances("alice", &[Coin::new(12345u128, "uatom")])
+///     .with_cw20_balance("mock_token", "bob", 67890)
+///     .with_cw20_token_info("mock_token", "Mock Token", "MOCK", 6)
+///     .with_cw20_allowance("mock_token", "bob", "alice", 100, Expiration::Never {});
+///
+/// let deps = mock_dependencies_with_querier(querier);
+/// ```
 pub struct CustomMockQuerier {
-    base: MockQuerier<Empty>,
-    cw20_querier: Cw20Querier,
+    pub(super) base: MockQuerier<Empty>,
+    pub(super) cw20_querier: Cw20Querier,
+    pub(super) block: BlockInfo,
+    /// The balances set on the bank querier, which can't be listed through
+    /// non-deprecated queries
+    pub(super) base_balances: BTreeMap<String, Vec<Coin>>,
+    denom_metadata: Vec<DenomMetadata>,
 }
 
 impl Default for CustomMockQuerier {
@@ -16,6 +44,9 @@ impl Default for CustomMockQuerier {
         CustomMockQuerier {
             base: MockQuerier::<Empty>::new(&[]),
             cw20_querier: Cw20Querier::default(),
+            block: mock_env().block,
+            base_balances: BTreeMap::new(),
+            denom_metadata: vec![],
         }
     }
 }
@@ -37,6 +68,7 @@ impl Querier for CustomMockQuerier {
 }
 
 impl CustomMockQuerier {
+    /// Answer a query request
     pub fn handle_query(&self, request: &QueryRequest<Empty>) -> QuerierResult {
         match request {
             QueryRequest::Wasm(WasmQuery::Smart {
@@ -57,11 +89,240 @@ impl CustomMockQuerier {
         }
     }
 
+    /// Set an account's balances of native coins
     pub fn set_base_balances(&mut self, address: &str, balances: &[Coin]) {
+        self.base_balances.insert(address.to_string(), balances.to_vec());
         self.base.bank.update_balance(address, balances.to_vec());
     }
 
+    /// Set the metadata of a native coin, as returned by
+    /// `BankQuery::DenomMetadata` and `BankQuery::AllDenomMetadata`
+    pub fn set_denom_metadata(&mut self, metadata: DenomMetadata) {
+        self.denom_metadata.retain(|m| m.base != metadata.base);
+        self.denom_metadata.push(metadata);
+        self.base.bank.set_denom_metadata(&self.denom_metadata);
+    }
+
+    /// Set an account's balance of a CW20 token
     pub fn set_cw20_balance(&mut self, contract: &str, user: &str, balance: u128) {
         self.cw20_querier.set_balance(contract, user, balance);
     }
+
+    /// Set the name, symbol and decimals of a CW20 token
+    pub fn set_cw20_token_info(&mut self, contract: &str, name: &str, symbol: &str, decimals: u8) {
+        self.cw20_querier.set_token_info(contract, name, symbol, decimals);
+    }
+
+    /// Set the amount of a CW20 token that `spender` is allowed to draw from
+    /// `owner`'s account
+    pub fn set_cw20_allowance(
+        &mut self,
+        contract: &str,
+        owner: &str,
+        spender: &str,
+        allowance: u128,
+        expires: Expiration,
+    ) {
+        self.cw20_querier.set_allowance(contract, owner, spender, allowance, expires);
+    }
+
+    /// Set the minter and supply cap of a CW20 token
+    pub fn set_cw20_minter(&mut self, contract: &str, minter: &str, cap: Option<u128>) {
+        self.cw20_querier.set_minter(contract, minter, cap);
+    }
+
+    /// Set the marketing info of a CW20 token
+    pub fn set_cw20_marketing_info(
+        &mut self,
+        contract: &str,
+        marketing_info: MarketingInfoResponse,
+    ) {
+        self.cw20_querier.set_marketing_info(contract, marketing_info);
+    }
+
+    /// Set the embedded logo of a CW20 token
+    pub fn set_cw20_logo(&mut self, contract: &str, logo: EmbeddedLogo) {
+        self.cw20_querier.set_logo(contract, logo);
+    }
+
+    /// Return the underlying CW20 querier, e.g. to inspect a token's state
+    pub fn cw20_querier(&self) -> &Cw20Querier {
+        &self.cw20_querier
+    }
+
+    /// Return a mutable reference to the underlying CW20 querier
+    pub fn cw20_querier_mut(&mut self) -> &mut Cw20Querier {
+        &mut self.cw20_querier
+    }
+
+    /// Seed an account's balances of native coins
+    pub fn with_base_balances(mut self, address: &str, balances: &[Coin]) -> Self {
+        self.set_base_balances(address, balances);
+        self
+    }
+
+    /// Seed the metadata of a native coin
+    pub fn with_denom_metadata(mut self, metadata: DenomMetadata) -> Self {
+        self.set_denom_metadata(metadata);
+        self
+    }
+
+    /// Seed an account's balance of a CW20 token
+    pub fn with_cw20_balance(mut self, contract: &str, user: &str, balance: u128) -> Self {
+        self.set_cw20_balance(contract, user, balance);
+        self
+    }
+
+    /// Seed the name, symbol and decimals of a CW20 token
+    pub fn with_cw20_token_info(
+        mut self,
+        contract: &str,
+        name: &str,
+        symbol: &str,
+        decimals: u8,
+    ) -> Self {
+        self.set_cw20_token_info(contract, name, symbol, decimals);
+        self
+    }
+
+    /// Seed the amount of a CW20 token that `spender` is allowed to draw from
+    /// `owner`'s account
+    pub fn with_cw20_allowance(
+        mut self,
+        contract: &str,
+        owner: &str,
+        spender: &str,
+        allowance: u128,
+        expires: Expiration,
+    ) -> Self {
+        self.set_cw20_allowance(contract, owner, spender, allowance, expires);
+        self
+    }
+
+    /// Seed the minter and supply cap of a CW20 token
+    pub fn with_cw20_minter(mut self, contract: &str, minter: &str, cap: Option<u128>) -> Self {
+        self.set_cw20_minter(contract, minter, cap);
+        self
+    }
+
+    /// Seed the marketing info of a CW20 token
+    pub fn with_cw20_marketing_info(
+        mut self,
+        contract: &str,
+        marketing_info: MarketingInfoResponse,
+    ) -> Self {
+        self.set_cw20_marketing_info(contract, marketing_info);
+        self
+    }
+
+    /// Seed the embedded logo of a CW20 token
+    pub fn with_cw20_logo(mut self, contract: &str, logo: EmbeddedLogo) -> Self {
+        self.set_cw20_logo(contract, logo);
+        self
+    }
+}
+
+//------------------------------------------------------------------------------
+// Tests
+//------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::{QuerierWrapper, Uint128};
+    use cw20::{AllowanceResponse, MinterResponse, TokenInfoResponse};
+
+    use super::*;
+    use crate::AssetInfo;
+
+    fn mock_token_info() -> TokenInfoResponse {
+        TokenInfoResponse {
+            name: "Mock Token".into(),
+            symbol: "MOCK".into(),
+            decimals: 6,
+            total_supply: Uint128::new(67890),
+        }
+    }
+
+    #[cfg(feature = "testing")]
+    #[test]
+    fn querying_denom_metadata() {
+        let mock_metadata = |base: &str, name: &str| DenomMetadata {
+            base: base.into(),
+            name: name.into(),
+            ..Default::default()
+        };
+
+        let mut querier = CustomMockQuerier::default()
+            .with_denom_metadata(mock_metadata("uatom", "Atom"))
+            .with_denom_metadata(mock_metadata("uosmo", "Osmo"));
+
+        // metadata of the same denom is replaced
+        querier.set_denom_metadata(mock_metadata("uatom", "Cosmos Hub Atom"));
+
+        let querier = QuerierWrapper::<Empty>::new(&querier);
+        assert_eq!(
+            querier.query_denom_metadata("uatom").unwrap(),
+            mock_metadata("uatom", "Cosmos Hub Atom"),
+        );
+        assert_eq!(querier.query_denom_metadata("uosmo").unwrap(), mock_metadata("uosmo", "Osmo"));
+        assert!(querier.query_denom_metadata("uusd").is_err());
+    }
+
+    #[test]
+    fn building() {
+        let querier = CustomMockQuerier::default()
+            .with_base_balances("alice", &[Coin::new(12345u128, "uatom")])
+            .with_cw20_balance("mock_token", "bob", 67890)
+            .with_cw20_token_info("mock_token", "Mock Token", "MOCK", 6)
+            .with_cw20_allowance("mock_token", "bob", "alice", 100, Expiration::AtHeight(10))
+            .with_cw20_minter("mock_token", "larry", None);
+        let querier = QuerierWrapper::<Empty>::new(&querier);
+
+        let balance = AssetInfo::native("uatom").query_balance(&querier, "alice").unwrap();
+        assert_eq!(balance, Uint128::new(12345));
+
+        let balance =
+            AssetInfo::cw20(Addr::unchecked("mock_token")).query_balance(&querier, "bob").unwrap();
+        assert_eq!(balance, Uint128::new(67890));
+
+        let token_info: TokenInfoResponse =
+            querier.query_wasm_smart("mock_token", &Cw20QueryMsg::TokenInfo {}).unwrap();
+        assert_eq!(token_info, mock_token_info());
+
+        let minter: Option<MinterResponse> =
+            querier.query_wasm_smart("mock_token", &Cw20QueryMsg::Minter {}).unwrap();
+        assert_eq!(minter.unwrap().minter, "larry");
+
+        let allowance: AllowanceResponse = querier
+            .query_wasm_smart(
+                "mock_token",
+                &Cw20QueryMsg::Allowance {
+                    owner: "bob".into(),
+                    spender: "alice".into(),
+                },
+            )
+            .unwrap();
+        assert_eq!(
+            allowance,
+            AllowanceResponse {
+                allowance: Uint128::new(100),
+                expires: Expiration::AtHeight(10),
+            },
+        );
+
+        let allowance: AllowanceResponse = querier
+            .query_wasm_smart(
+                "mock_token",
+                &Cw20QueryMsg::Allowance {
+                    owner: "alice".into(),
+                    spender: "bob".into(),
+                },
+            )
+            .unwrap();
+        assert_eq!(allowance, AllowanceResponse::default());
+
+        let err = querier
+            .query_wasm_smart::<TokenInfoResponse>("mock_token2", &Cw20QueryMsg::TokenInfo {});
+        assert!(err.is_err());
+    }
 }
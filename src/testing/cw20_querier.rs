@@ -1,63 +1,602 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
-use cosmwasm_std::{to_json_binary, Addr, QuerierResult, SystemError, Uint128};
-use cw20::{BalanceResponse, Cw20QueryMsg};
+use cosmwasm_schema::serde::Serialize;
+use cosmwasm_std::{
+    to_json_binary, Addr, Binary, BlockInfo, QuerierResult, StdError, StdResult, SystemError,
+    Uint128,
+};
+use cw20::{
+    AllAccountsResponse, AllAllowancesResponse, AllowanceInfo, AllowanceResponse, BalanceResponse,
+    Cw20ExecuteMsg, Cw20QueryMsg, DownloadLogoResponse, EmbeddedLogo, Expiration, LogoInfo,
+    MarketingInfoResponse, MinterResponse, TokenInfoResponse,
+};
 
-#[derive(Default)]
-pub struct Cw20Querier {
-    balances: HashMap<Addr, HashMap<Addr, Uint128>>,
+// same pagination limits as the cw20-base contract
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
+
+/// The name, symbol and decimals of a mock CW20 token
+///
+/// Unlike `TokenInfoResponse`, there is no total supply, as it is always the
+/// sum of all accounts' balances.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Cw20TokenInfo {
+    /// Name of the token, e.g. `Mock Token`
+    pub name: String,
+    /// Ticker symbol of the token, e.g. `MOCK`
+    pub symbol: String,
+    /// Number of decimal places of the token's amounts
+    pub decimals: u8,
 }
 
-impl Cw20Querier {
-    pub fn handle_query(&self, contract_addr: &Addr, query: Cw20QueryMsg) -> QuerierResult {
+/// The mock state of a single CW20 token contract
+#[derive(Clone, Debug, Default)]
+pub struct Cw20State {
+    /// Name, symbol and decimals; `None` if not set
+    pub token_info: Option<Cw20TokenInfo>,
+    /// Balances of each account
+    pub balances: BTreeMap<Addr, Uint128>,
+    /// Allowances, keyed by owner and then spender
+    pub allowances: BTreeMap<(Addr, Addr), AllowanceResponse>,
+    /// Minter and supply cap; `None` if the token is not mintable
+    pub minter: Option<MinterResponse>,
+    /// Marketing info
+    pub marketing_info: MarketingInfoResponse,
+    /// Embedded logo; `None` if the token does not have one
+    pub logo: Option<DownloadLogoResponse>,
+}
+
+impl Cw20State {
+    /// Return the total supply of the token, which is always the sum of all
+    /// accounts' balances
+    pub fn total_supply(&self) -> Uint128 {
+        self.balances.values().sum()
+    }
+
+    /// Apply a CW20 execute message sent by `sender` to the state, the same way
+    /// the cw20-base contract would
+    ///
+    /// Supports transferring, sending, burning (either directly or by using an
+    /// allowance) and minting tokens. Hooks of `Send` messages are not invoked.
+    ///
+    /// If the message is rejected, e.g. due to insufficient balance or
+    /// allowance, the state is left unchanged.
+    pub fn execute(
+        &mut self,
+        block: &BlockInfo,
+        sender: &Addr,
+        msg: Cw20ExecuteMsg,
+    ) -> StdResult<()> {
+        let mut state = self.clone();
+        state.apply(block, sender, msg)?;
+        *self = state;
+        Ok(())
+    }
+
+    fn apply(&mut self, block: &BlockInfo, sender: &Addr, msg: Cw20ExecuteMsg) -> StdResult<()> {
+        match msg {
+            Cw20ExecuteMsg::Transfer {
+                recipient,
+                amount,
+            } => self.transfer(sender, &Addr::unchecked(recipient), amount),
+
+            Cw20ExecuteMsg::Send {
+                contract,
+                amount,
+                ..
+            } => self.transfer(sender, &Addr::unchecked(contract), amount),
+
+            Cw20ExecuteMsg::TransferFrom {
+                owner,
+                recipient,
+                amount,
+            } => {
+                let owner = Addr::unchecked(owner);
+                self.spend_allowance(block, &owner, sender, amount)?;
+                self.transfer(&owner, &Addr::unchecked(recipient), amount)
+            },
+
+            Cw20ExecuteMsg::SendFrom {
+                owner,
+                contract,
+                amount,
+                ..
+            } => {
+                let owner = Addr::unchecked(owner);
+                self.spend_allowance(block, &owner, sender, amount)?;
+                self.transfer(&owner, &Addr::unchecked(contract), amount)
+            },
+
+            Cw20ExecuteMsg::Burn {
+                amount,
+            } => self.decrease_balance(sender, amount),
+
+            Cw20ExecuteMsg::BurnFrom {
+                owner,
+                amount,
+            } => {
+                let owner = Addr::unchecked(owner);
+                self.spend_allowance(block, &owner, sender, amount)?;
+                self.decrease_balance(&owner, amount)
+            },
+
+            Cw20ExecuteMsg::Mint {
+                recipient,
+                amount,
+            } => {
+                let minter = self
+                    .minter
+                    .as_ref()
+                    .ok_or_else(|| StdError::generic_err("[mock]: cw20 token is not mintable"))?;
+
+                if minter.minter != sender.as_str() {
+                    return Err(StdError::generic_err(format!(
+                        "[mock]: {sender} is not the minter of the cw20 token"
+                    )));
+                }
+
+                if let Some(cap) = minter.cap {
+                    if self.total_supply().checked_add(amount)? > cap {
+                        return Err(StdError::generic_err("[mock]: cw20 minting cap exceeded"));
+                    }
+                }
+
+                self.increase_balance(&Addr::unchecked(recipient), amount)
+            },
+
+            msg => Err(StdError::generic_err(format!("[mock]: unsupported cw20 execute {msg:?}"))),
+        }
+    }
+
+    fn increase_balance(&mut self, account: &Addr, amount: Uint128) -> StdResult<()> {
+        let balance = self.balances.entry(account.clone()).or_default();
+        *balance = balance.checked_add(amount)?;
+        Ok(())
+    }
+
+    fn decrease_balance(&mut self, account: &Addr, amount: Uint128) -> StdResult<()> {
+        let balance = self.balances.get(account).copied().unwrap_or_default();
+        let new_balance = balance.checked_sub(amount).map_err(|_| {
+            StdError::generic_err(format!(
+                "[mock]: insufficient cw20 balance: {account} has {balance}, needs {amount}"
+            ))
+        })?;
+        self.balances.insert(account.clone(), new_balance);
+        Ok(())
+    }
+
+    fn transfer(&mut self, from: &Addr, to: &Addr, amount: Uint128) -> StdResult<()> {
+        self.decrease_balance(from, amount)?;
+        self.increase_balance(to, amount)
+    }
+
+    fn spend_allowance(
+        &mut self,
+        block: &BlockInfo,
+        owner: &Addr,
+        spender: &Addr,
+        amount: Uint128,
+    ) -> StdResult<()> {
+        let key = (owner.clone(), spender.clone());
+        let allowance = self.allowances.get_mut(&key).ok_or_else(|| {
+            StdError::generic_err(format!("[mock]: {spender} has no cw20 allowance from {owner}"))
+        })?;
+
+        if allowance.expires.is_expired(block) {
+            return Err(StdError::generic_err(format!(
+                "[mock]: cw20 allowance of {spender} from {owner} is expired"
+            )));
+        }
+
+        allowance.allowance = allowance.allowance.checked_sub(amount).map_err(|_| {
+            StdError::generic_err(format!(
+                "[mock]: insufficient cw20 allowance: {spender} is allowed {} from {owner}, needs {amount}",
+                allowance.allowance,
+            ))
+        })?;
+
+        Ok(())
+    }
+
+    fn handle_query(&self, query: Cw20QueryMsg) -> QuerierResult {
         match query {
             Cw20QueryMsg::Balance {
                 address,
             } => {
-                let contract_balances = match self.balances.get(contract_addr) {
-                    Some(balances) => balances,
-                    None => {
-                        return Err(SystemError::InvalidRequest {
-                            error: format!(
-                                "[mock]: cw20 balances not set for token {contract_addr:?}",
-                            ),
-                            request: Default::default(),
-                        })
-                        .into()
-                    },
-                };
-
-                let balance = match contract_balances.get(&Addr::unchecked(&address)) {
-                    Some(balance) => balance,
-                    None => {
-                        return Err(SystemError::InvalidRequest {
-                            error: format!("[mock]: cw20 balance not set for user {address:?}"),
-                            request: Default::default(),
-                        })
-                        .into()
-                    },
-                };
-
-                Ok(to_json_binary(&BalanceResponse {
-                    balance: *balance,
+                // like the actual cw20-base contract, return zero balance if not set
+                let balance =
+                    self.balances.get(&Addr::unchecked(address)).copied().unwrap_or_default();
+                ok(&BalanceResponse {
+                    balance,
                 })
-                .into())
-                .into()
             },
 
-            query => Err(SystemError::InvalidRequest {
-                error: format!("[mock]: unsupported cw20 query {query:?}"),
-                request: Default::default(),
-            })
-            .into(),
+            Cw20QueryMsg::TokenInfo {} => match &self.token_info {
+                Some(info) => ok(&TokenInfoResponse {
+                    name: info.name.clone(),
+                    symbol: info.symbol.clone(),
+                    decimals: info.decimals,
+                    total_supply: self.total_supply(),
+                }),
+                None => err("[mock]: cw20 token info not set".into()),
+            },
+
+            Cw20QueryMsg::Allowance {
+                owner,
+                spender,
+            } => {
+                // like the actual cw20-base contract, return zero allowance if not set
+                let key = (Addr::unchecked(owner), Addr::unchecked(spender));
+                ok(&self.allowances.get(&key).cloned().unwrap_or_default())
+            },
+
+            Cw20QueryMsg::Minter {} => ok(&self.minter),
+
+            Cw20QueryMsg::MarketingInfo {} => ok(&self.marketing_info),
+
+            Cw20QueryMsg::DownloadLogo {} => match &self.logo {
+                Some(logo) => ok(logo),
+                None => err("[mock]: cw20 logo not set".into()),
+            },
+
+            Cw20QueryMsg::AllAllowances {
+                owner,
+                start_after,
+                limit,
+            } => {
+                let owner = Addr::unchecked(owner);
+                let allowances = self
+                    .allowances
+                    .iter()
+                    .filter(|((o, _), _)| *o == owner)
+                    .filter(|((_, spender), _)| match &start_after {
+                        Some(start_after) => spender.as_str() > start_after.as_str(),
+                        None => true,
+                    })
+                    .take(limit_or_default(limit))
+                    .map(|((_, spender), allowance)| AllowanceInfo {
+                        spender: spender.to_string(),
+                        allowance: allowance.allowance,
+                        expires: allowance.expires,
+                    })
+                    .collect();
+
+                ok(&AllAllowancesResponse {
+                    allowances,
+                })
+            },
+
+            Cw20QueryMsg::AllAccounts {
+                start_after,
+                limit,
+            } => {
+                let accounts = self
+                    .balances
+                    .keys()
+                    .filter(|account| match &start_after {
+                        Some(start_after) => account.as_str() > start_after.as_str(),
+                        None => true,
+                    })
+                    .take(limit_or_default(limit))
+                    .map(|account| account.to_string())
+                    .collect();
+
+                ok(&AllAccountsResponse {
+                    accounts,
+                })
+            },
+        }
+    }
+}
+
+fn limit_or_default(limit: Option<u32>) -> usize {
+    limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize
+}
+
+fn ok<T: Serialize>(response: &T) -> QuerierResult {
+    match to_json_binary(response) {
+        Ok(bin) => Ok(Ok::<_, String>(bin).into()).into(),
+        Err(e) => err(format!("[mock]: failed to serialize response {e}")),
+    }
+}
+
+fn err(error: String) -> QuerierResult {
+    Err(SystemError::InvalidRequest {
+        error,
+        request: Default::default(),
+    })
+    .into()
+}
+
+/// A mock querier that answers queries to CW20 token contracts
+///
+/// Each contract has its own [`Cw20State`], which is created as soon as any of
+/// its data is set. Queries to contracts without a state are rejected, as if
+/// the contract did not exist.
+#[derive(Default)]
+pub struct Cw20Querier {
+    contracts: HashMap<Addr, Cw20State>,
+}
+
+impl Cw20Querier {
+    /// Answer a query made to the CW20 contract of the given address
+    pub fn handle_query(&self, contract_addr: &Addr, query: Cw20QueryMsg) -> QuerierResult {
+        match self.contracts.get(contract_addr) {
+            Some(state) => state.handle_query(query),
+            None => err(format!("[mock]: cw20 state not set for token {contract_addr:?}")),
         }
     }
 
+    /// Return the state of the given CW20 token, if any of its data is set
+    pub fn state(&self, contract: &str) -> Option<&Cw20State> {
+        self.contracts.get(&Addr::unchecked(contract))
+    }
+
+    /// Return a mutable reference to the state of the given CW20 token; create
+    /// an empty state if not exists
+    pub fn state_mut(&mut self, contract: &str) -> &mut Cw20State {
+        self.contracts.entry(Addr::unchecked(contract)).or_default()
+    }
+
+    /// Set a user's balance of the given CW20 token
     pub fn set_balance(&mut self, contract: &str, user: &str, balance: u128) {
-        let contract_addr = Addr::unchecked(contract);
-        let user_addr = Addr::unchecked(user);
+        self.state_mut(contract).balances.insert(Addr::unchecked(user), Uint128::new(balance));
+    }
+
+    /// Set the name, symbol and decimals of the given CW20 token
+    ///
+    /// The total supply is not set here, as it is always the sum of all balances.
+    pub fn set_token_info(&mut self, contract: &str, name: &str, symbol: &str, decimals: u8) {
+        self.state_mut(contract).token_info = Some(Cw20TokenInfo {
+            name: name.into(),
+            symbol: symbol.into(),
+            decimals,
+        });
+    }
+
+    /// Set the amount of the given CW20 token that `spender` is allowed to draw
+    /// from `owner`'s account
+    pub fn set_allowance(
+        &mut self,
+        contract: &str,
+        owner: &str,
+        spender: &str,
+        allowance: u128,
+        expires: Expiration,
+    ) {
+        let key = (Addr::unchecked(owner), Addr::unchecked(spender));
+        self.state_mut(contract).allowances.insert(
+            key,
+            AllowanceResponse {
+                allowance: Uint128::new(allowance),
+                expires,
+            },
+        );
+    }
+
+    /// Set the minter and supply cap of the given CW20 token
+    pub fn set_minter(&mut self, contract: &str, minter: &str, cap: Option<u128>) {
+        self.state_mut(contract).minter = Some(MinterResponse {
+            minter: minter.into(),
+            cap: cap.map(Uint128::new),
+        });
+    }
+
+    /// Set the marketing info of the given CW20 token
+    pub fn set_marketing_info(&mut self, contract: &str, marketing_info: MarketingInfoResponse) {
+        self.state_mut(contract).marketing_info = marketing_info;
+    }
+
+    /// Set the embedded logo of the given CW20 token; also update the logo in its
+    /// marketing info to indicate that an embedded logo is available
+    pub fn set_logo(&mut self, contract: &str, logo: EmbeddedLogo) {
+        let (mime_type, data): (&str, Binary) = match logo {
+            EmbeddedLogo::Svg(data) => ("image/svg+xml", data),
+            EmbeddedLogo::Png(data) => ("image/png", data),
+        };
+
+        let state = self.state_mut(contract);
+        state.marketing_info.logo = Some(LogoInfo::Embedded);
+        state.logo = Some(DownloadLogoResponse {
+            mime_type: mime_type.into(),
+            data,
+        });
+    }
+}
+
+//------------------------------------------------------------------------------
+// Tests
+//------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_schema::serde::de::DeserializeOwned;
+    use cosmwasm_std::{from_json, ContractResult, SystemResult};
+
+    use super::*;
+
+    fn query<T: DeserializeOwned>(querier: &Cw20Querier, query: Cw20QueryMsg) -> T {
+        match querier.handle_query(&Addr::unchecked("mock_token"), query) {
+            SystemResult::Ok(ContractResult::Ok(bin)) => from_json(bin).unwrap(),
+            res => panic!("query failed: {res:?}"),
+        }
+    }
+
+    fn mock_querier() -> Cw20Querier {
+        let mut querier = Cw20Querier::default();
+        querier.set_token_info("mock_token", "Mock Token", "MOCK", 6);
+        querier.set_minter("mock_token", "larry", Some(1000000));
+        for i in 0..15 {
+            querier.set_balance("mock_token", &format!("user{i:02}"), 100);
+        }
+        querier.set_allowance("mock_token", "user00", "user02", 20, Expiration::AtHeight(10));
+        querier.set_allowance("mock_token", "user00", "user01", 10, Expiration::Never {});
+        querier.set_allowance("mock_token", "user01", "user00", 30, Expiration::Never {});
+        querier
+    }
+
+    #[test]
+    fn querying_token_info() {
+        let querier = mock_querier();
+
+        let res: TokenInfoResponse = query(&querier, Cw20QueryMsg::TokenInfo {});
+        assert_eq!(
+            res,
+            TokenInfoResponse {
+                name: "Mock Token".into(),
+                symbol: "MOCK".into(),
+                decimals: 6,
+                total_supply: Uint128::new(1500),
+            },
+        );
+        assert_eq!(
+            querier.state("mock_token").unwrap().token_info,
+            Some(Cw20TokenInfo {
+                name: "Mock Token".into(),
+                symbol: "MOCK".into(),
+                decimals: 6,
+            }),
+        );
+
+        let res: Option<MinterResponse> = query(&querier, Cw20QueryMsg::Minter {});
+        assert_eq!(
+            res,
+            Some(MinterResponse {
+                minter: "larry".into(),
+                cap: Some(Uint128::new(1000000)),
+            }),
+        );
+
+        let res = querier.handle_query(&Addr::unchecked("mock_token2"), Cw20QueryMsg::Minter {});
+        assert!(matches!(res, SystemResult::Err(_)));
+    }
+
+    #[test]
+    fn querying_balances() {
+        let querier = mock_querier();
+
+        let res: BalanceResponse = query(
+            &querier,
+            Cw20QueryMsg::Balance {
+                address: "user00".into(),
+            },
+        );
+        assert_eq!(res.balance, Uint128::new(100));
+
+        let res: BalanceResponse = query(
+            &querier,
+            Cw20QueryMsg::Balance {
+                address: "larry".into(),
+            },
+        );
+        assert_eq!(res.balance, Uint128::zero());
 
-        let contract_balances = self.balances.entry(contract_addr).or_default();
-        contract_balances.insert(user_addr, Uint128::new(balance));
+        let res: AllAccountsResponse = query(
+            &querier,
+            Cw20QueryMsg::AllAccounts {
+                start_after: None,
+                limit: None,
+            },
+        );
+        assert_eq!(res.accounts.len(), 10);
+        assert_eq!(res.accounts[0], "user00");
+
+        let res: AllAccountsResponse = query(
+            &querier,
+            Cw20QueryMsg::AllAccounts {
+                start_after: Some("user09".into()),
+                limit: Some(100),
+            },
+        );
+        assert_eq!(res.accounts, (10..15).map(|i| format!("user{i}")).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn querying_allowances() {
+        let querier = mock_querier();
+
+        let res: AllowanceResponse = query(
+            &querier,
+            Cw20QueryMsg::Allowance {
+                owner: "user00".into(),
+                spender: "user02".into(),
+            },
+        );
+        assert_eq!(
+            res,
+            AllowanceResponse {
+                allowance: Uint128::new(20),
+                expires: Expiration::AtHeight(10),
+            },
+        );
+
+        let res: AllAllowancesResponse = query(
+            &querier,
+            Cw20QueryMsg::AllAllowances {
+                owner: "user00".into(),
+                start_after: None,
+                limit: None,
+            },
+        );
+        assert_eq!(
+            res.allowances,
+            vec![
+                AllowanceInfo {
+                    spender: "user01".into(),
+                    allowance: Uint128::new(10),
+                    expires: Expiration::Never {},
+                },
+                AllowanceInfo {
+                    spender: "user02".into(),
+                    allowance: Uint128::new(20),
+                    expires: Expiration::AtHeight(10),
+                },
+            ],
+        );
+
+        let res: AllAllowancesResponse = query(
+            &querier,
+            Cw20QueryMsg::AllAllowances {
+                owner: "user00".into(),
+                start_after: Some("user01".into()),
+                limit: Some(1),
+            },
+        );
+        assert_eq!(res.allowances.len(), 1);
+        assert_eq!(res.allowances[0].spender, "user02");
+    }
+
+    #[test]
+    fn querying_marketing_info() {
+        let mut querier = mock_querier();
+
+        let res: MarketingInfoResponse = query(&querier, Cw20QueryMsg::MarketingInfo {});
+        assert_eq!(res, MarketingInfoResponse::default());
+
+        let res =
+            querier.handle_query(&Addr::unchecked("mock_token"), Cw20QueryMsg::DownloadLogo {});
+        assert!(matches!(res, SystemResult::Err(_)));
+
+        querier.set_marketing_info(
+            "mock_token",
+            MarketingInfoResponse {
+                project: Some("Mock Project".into()),
+                ..Default::default()
+            },
+        );
+        querier.set_logo("mock_token", EmbeddedLogo::Png(Binary::from(b"mock_png".to_vec())));
+
+        let res: MarketingInfoResponse = query(&querier, Cw20QueryMsg::MarketingInfo {});
+        assert_eq!(res.project, Some("Mock Project".into()));
+        assert_eq!(res.logo, Some(LogoInfo::Embedded));
+
+        let res: DownloadLogoResponse = query(&querier, Cw20QueryMsg::DownloadLogo {});
+        assert_eq!(
+            res,
+            DownloadLogoResponse {
+                mime_type: "image/png".into(),
+                data: Binary::from(b"mock_png".to_vec()),
+            },
+        );
     }
 }
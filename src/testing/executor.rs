@@ -0,0 +1,363 @@
+use std::collections::BTreeMap;
+
+use cosmwasm_std::{
+    from_json, Addr, BankMsg, BlockInfo, Coin, CosmosMsg, StdError, StdResult, WasmMsg,
+};
+use cw20::Cw20ExecuteMsg;
+
+use super::CustomMockQuerier;
+
+/// A minimal simulation of message execution, so that tests can assert that the
+/// messages generated by `Asset` and `AssetList` methods actually move balances
+/// as expected.
+///
+/// Supported messages are:
+///
+/// - `BankMsg::Send`, which moves native coins between accounts;
+/// - `WasmMsg::Execute` to a CW20 token contract with a known state, which is
+///   applied to the token's balances as described in
+///   [`Cw20State::execute`](super::Cw20State::execute). Native coins attached as
+///   funds are moved to the token contract.
+///
+/// Once executed, the new balances are returned by subsequent queries, e.g. by
+/// `AssetInfo::query_balance`:
+///
+/// ```rust
+/// use cosmwasm_std::{Coin, QuerierWrapper, Uint128};
+/// use cw_asset::{testing::CustomMockQuerier, Asset, AssetInfo};
+///
+/// let mut querier =
+///     CustomMockQuerier::default().with_base_balances("alice", &[Coin::new(12345u128, "uatom")]);
+///
+/// let msg = Asset::native("uatom", 10000u128).transfer_msg("bob").unwrap();
+/// querier.execute("alice", &msg).unwrap();
+///
+/// let balance =
+///     AssetInfo::native("uatom").query_balance(&QuerierWrapper::new(&querier), "bob").unwrap();
+/// assert_eq!(balance, Uint128::new(10000));
+/// ```
+impl CustomMockQuerier {
+    /// Set the block info used to determine whether CW20 allowances are expired
+    pub fn set_block(&mut self, block: BlockInfo) {
+        self.block = block;
+    }
+
+    /// Seed the block info used to determine whether CW20 allowances are expired
+    pub fn with_block(mut self, block: BlockInfo) -> Self {
+        self.set_block(block);
+        self
+    }
+
+    /// Execute a message on behalf of `sender`, and apply its effects to the
+    /// mock balances
+    ///
+    /// If the message is rejected, e.g. due to insufficient funds or allowance,
+    /// neither the bank balances nor the CW20 states are changed.
+    pub fn execute(&mut self, sender: &str, msg: &CosmosMsg) -> StdResult<()> {
+        let sender_addr = Addr::unchecked(sender);
+
+        match msg {
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address,
+                amount,
+            }) => {
+                let balances = self.bank_send(sender, to_address, amount)?;
+                self.commit_balances(balances);
+                Ok(())
+            },
+
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr,
+                msg,
+                funds,
+            }) => {
+                if self.cw20_querier.state(contract_addr).is_none() {
+                    return Err(StdError::generic_err(format!(
+                        "[mock]: cw20 state not set for token {contract_addr}"
+                    )));
+                }
+
+                let cw20_msg: Cw20ExecuteMsg = from_json(msg)?;
+
+                // the funds are only moved if the cw20 message succeeds, which
+                // itself leaves the token's state unchanged if it fails
+                let balances = self.bank_send(sender, contract_addr, funds)?;
+                let block = self.block.clone();
+                self.cw20_querier.state_mut(contract_addr).execute(
+                    &block,
+                    &sender_addr,
+                    cw20_msg,
+                )?;
+                self.commit_balances(balances);
+                Ok(())
+            },
+
+            msg => Err(StdError::generic_err(format!("[mock]: unsupported message {msg:?}"))),
+        }
+    }
+
+    /// Execute multiple messages on behalf of `sender`, in order
+    ///
+    /// NOTE: Execution stops at the first failing message, but the effects of
+    /// messages executed before it are not reverted.
+    pub fn execute_many(&mut self, sender: &str, msgs: &[CosmosMsg]) -> StdResult<()> {
+        msgs.iter().try_for_each(|msg| self.execute(sender, msg))
+    }
+
+    /// Compute the bank balances of `from` and `to` after sending `coins`,
+    /// without applying them
+    fn bank_send(
+        &self,
+        from: &str,
+        to: &str,
+        coins: &[Coin],
+    ) -> StdResult<BTreeMap<String, Vec<Coin>>> {
+        let mut balances = BTreeMap::new();
+        if coins.is_empty() {
+            return Ok(balances);
+        }
+
+        // the bank querier can only overwrite an account's balances as a whole,
+        // so we need to know all of them. `from` and `to` may be the same
+        // account, so both are updated in a single table.
+        for account in [from, to] {
+            let account_balances = self.base_balances.get(account).cloned().unwrap_or_default();
+            balances.entry(account.to_string()).or_insert(account_balances);
+        }
+
+        for coin in coins {
+            let from_balances = balances.get_mut(from).unwrap();
+            match from_balances.iter_mut().find(|c| c.denom == coin.denom) {
+                Some(c) if c.amount >= coin.amount => c.amount -= coin.amount,
+                _ => {
+                    return Err(StdError::generic_err(format!(
+                        "[mock]: insufficient funds: {from} does not have {coin}"
+                    )))
+                },
+            }
+
+            let to_balances = balances.get_mut(to).unwrap();
+            match to_balances.iter_mut().find(|c| c.denom == coin.denom) {
+                Some(c) => c.amount = c.amount.checked_add(coin.amount)?,
+                None => to_balances.push(coin.clone()),
+            }
+        }
+
+        for account_balances in balances.values_mut() {
+            account_balances.retain(|c| !c.amount.is_zero());
+        }
+
+        Ok(balances)
+    }
+
+    fn commit_balances(&mut self, balances: BTreeMap<String, Vec<Coin>>) {
+        for (account, account_balances) in balances {
+            self.set_base_balances(&account, &account_balances);
+        }
+    }
+}
+
+//------------------------------------------------------------------------------
+// Tests
+//------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::{testing::mock_env, to_json_binary, Empty, QuerierWrapper, Uint128};
+    use cw20::Expiration;
+
+    use super::*;
+    use crate::{testing::mock_token, Asset, AssetInfo, AssetList};
+
+    fn mock_querier() -> CustomMockQuerier {
+        CustomMockQuerier::default()
+            .with_base_balances(
+                "alice",
+                &[Coin::new(100u128, "uatom"), Coin::new(200u128, "uosmo")],
+            )
+            .with_cw20_balance("mock_token", "alice", 300)
+            .with_cw20_allowance("mock_token", "alice", "bob", 50, Expiration::AtHeight(20000))
+            .with_cw20_minter("mock_token", "larry", Some(1000))
+    }
+
+    fn balance(querier: &CustomMockQuerier, info: &AssetInfo, address: &str) -> Uint128 {
+        info.query_balance(&QuerierWrapper::new(querier), address).unwrap()
+    }
+
+    #[test]
+    fn transferring() {
+        let mut querier = mock_querier();
+
+        let list = AssetList::from(vec![
+            Asset::native("uatom", 60u128),
+            Asset::native("uosmo", 200u128),
+            Asset::new(mock_token(), 100u128),
+        ]);
+        querier.execute_many("alice", &list.transfer_msgs("bob").unwrap()).unwrap();
+
+        let uatom = AssetInfo::native("uatom");
+        let uosmo = AssetInfo::native("uosmo");
+        assert_eq!(balance(&querier, &uatom, "alice"), Uint128::new(40));
+        assert_eq!(balance(&querier, &uatom, "bob"), Uint128::new(60));
+        assert_eq!(balance(&querier, &uosmo, "alice"), Uint128::zero());
+        assert_eq!(balance(&querier, &uosmo, "bob"), Uint128::new(200));
+        assert_eq!(balance(&querier, &mock_token(), "alice"), Uint128::new(200));
+        assert_eq!(balance(&querier, &mock_token(), "bob"), Uint128::new(100));
+
+        let msg = Asset::native("uatom", 41u128).transfer_msg("bob").unwrap();
+        assert!(querier.execute("alice", &msg).is_err());
+
+        let msg = Asset::new(mock_token(), 201u128).transfer_msg("bob").unwrap();
+        assert!(querier.execute("alice", &msg).is_err());
+    }
+
+    #[test]
+    fn transferring_from() {
+        let mut querier = mock_querier();
+        let asset = Asset::new(mock_token(), 30u128);
+
+        let msg = asset.transfer_from_msg("alice", "charlie").unwrap();
+        querier.execute("bob", &msg).unwrap();
+        assert_eq!(balance(&querier, &mock_token(), "alice"), Uint128::new(270));
+        assert_eq!(balance(&querier, &mock_token(), "charlie"), Uint128::new(30));
+
+        // only 20 of the allowance is left
+        let err = querier.execute("bob", &msg).unwrap_err();
+        assert!(err.to_string().contains("insufficient cw20 allowance"));
+
+        // no allowance at all
+        assert!(querier.execute("charlie", &msg).is_err());
+
+        // expired allowance
+        let mut block = mock_env().block;
+        block.height = 20000;
+        querier.set_block(block);
+        let msg = Asset::new(mock_token(), 10u128).transfer_from_msg("alice", "charlie").unwrap();
+        let err = querier.execute("bob", &msg).unwrap_err();
+        assert!(err.to_string().contains("expired"));
+    }
+
+    #[test]
+    fn sending_to_self() {
+        let mut querier = mock_querier();
+        let uatom = AssetInfo::native("uatom");
+
+        let msg = Asset::native("uatom", 60u128).transfer_msg("alice").unwrap();
+        querier.execute("alice", &msg).unwrap();
+        assert_eq!(balance(&querier, &uatom, "alice"), Uint128::new(100));
+
+        let msg = Asset::native("uatom", 101u128).transfer_msg("alice").unwrap();
+        assert!(querier.execute("alice", &msg).is_err());
+        assert_eq!(balance(&querier, &uatom, "alice"), Uint128::new(100));
+    }
+
+    #[test]
+    fn rejecting_without_side_effects() {
+        let mut querier = mock_querier();
+        let uatom = AssetInfo::native("uatom");
+        let allowance = |querier: &CustomMockQuerier| {
+            let key = (Addr::unchecked("alice"), Addr::unchecked("bob"));
+            querier.cw20_querier().state("mock_token").unwrap().allowances[&key].allowance
+        };
+
+        // insufficient native funds
+        let msg = Asset::native("uatom", 101u128).transfer_msg("bob").unwrap();
+        assert!(querier.execute("alice", &msg).is_err());
+        assert_eq!(balance(&querier, &uatom, "alice"), Uint128::new(100));
+        assert_eq!(balance(&querier, &uatom, "bob"), Uint128::zero());
+
+        // insufficient cw20 balance
+        let msg = Asset::new(mock_token(), 301u128).transfer_msg("bob").unwrap();
+        assert!(querier.execute("alice", &msg).is_err());
+        assert_eq!(balance(&querier, &mock_token(), "alice"), Uint128::new(300));
+        assert_eq!(balance(&querier, &mock_token(), "bob"), Uint128::zero());
+
+        // sufficient allowance, but insufficient balance
+        querier.set_cw20_balance("mock_token", "alice", 10);
+        let msg = Asset::new(mock_token(), 30u128).transfer_from_msg("alice", "charlie").unwrap();
+        assert!(querier.execute("bob", &msg).is_err());
+        assert_eq!(allowance(&querier), Uint128::new(50));
+        assert_eq!(balance(&querier, &mock_token(), "alice"), Uint128::new(10));
+        assert_eq!(balance(&querier, &mock_token(), "charlie"), Uint128::zero());
+
+        // insufficient allowance
+        querier.set_cw20_balance("mock_token", "alice", 100);
+        let msg = CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "mock_token".into(),
+            msg: to_json_binary(&Cw20ExecuteMsg::BurnFrom {
+                owner: "alice".into(),
+                amount: Uint128::new(51),
+            })
+            .unwrap(),
+            funds: vec![],
+        });
+        assert!(querier.execute("bob", &msg).is_err());
+        assert_eq!(allowance(&querier), Uint128::new(50));
+        assert_eq!(balance(&querier, &mock_token(), "alice"), Uint128::new(100));
+
+        // funds attached to a failing cw20 message are not moved
+        let msg = CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "mock_token".into(),
+            msg: to_json_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: "bob".into(),
+                amount: Uint128::new(101),
+            })
+            .unwrap(),
+            funds: vec![Coin::new(60u128, "uatom")],
+        });
+        assert!(querier.execute("alice", &msg).is_err());
+        assert_eq!(balance(&querier, &uatom, "alice"), Uint128::new(100));
+        assert_eq!(balance(&querier, &uatom, "mock_token"), Uint128::zero());
+        assert_eq!(balance(&querier, &mock_token(), "alice"), Uint128::new(100));
+    }
+
+    #[test]
+    fn sending_minting_and_burning() {
+        let mut querier = mock_querier();
+
+        let msg = Asset::new(mock_token(), 100u128)
+            .send_msg("mock_contract", to_json_binary(&Empty {}).unwrap())
+            .unwrap();
+        querier.execute("alice", &msg).unwrap();
+        assert_eq!(balance(&querier, &mock_token(), "mock_contract"), Uint128::new(100));
+
+        let mint_msg = |amount: u128| {
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "mock_token".into(),
+                msg: to_json_binary(&Cw20ExecuteMsg::Mint {
+                    recipient: "bob".into(),
+                    amount: Uint128::new(amount),
+                })
+                .unwrap(),
+                funds: vec![],
+            })
+        };
+        querier.execute("larry", &mint_msg(500)).unwrap();
+        assert_eq!(balance(&querier, &mock_token(), "bob"), Uint128::new(500));
+
+        // not the minter
+        assert!(querier.execute("alice", &mint_msg(1)).is_err());
+
+        // cap exceeded
+        assert!(querier.execute("larry", &mint_msg(201)).is_err());
+
+        let burn_msg = CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "mock_token".into(),
+            msg: to_json_binary(&Cw20ExecuteMsg::Burn {
+                amount: Uint128::new(500),
+            })
+            .unwrap(),
+            funds: vec![],
+        });
+        querier.execute("bob", &burn_msg).unwrap();
+        assert_eq!(balance(&querier, &mock_token(), "bob"), Uint128::zero());
+        assert_eq!(
+            querier.cw20_querier().state("mock_token").unwrap().total_supply(),
+            Uint128::new(300)
+        );
+
+        // unknown token
+        let msg = Asset::cw20(Addr::unchecked("mock_token2"), 1u128).transfer_msg("bob").unwrap();
+        assert!(querier.execute("alice", &msg).is_err());
+    }
+}
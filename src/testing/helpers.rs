@@ -7,11 +7,37 @@ use cosmwasm_std::{
 
 use super::CustomMockQuerier;
 
+/// Create mock dependencies with an empty [`CustomMockQuerier`]
 pub fn mock_dependencies() -> OwnedDeps<MockStorage, MockApi, CustomMockQuerier> {
+    mock_dependencies_with_querier(CustomMockQuerier::default())
+}
+
+/// Create mock dependencies with the given [`CustomMockQuerier`], e.g. one
+/// seeded with balances using its `with_*` methods
+pub fn mock_dependencies_with_querier(
+    querier: CustomMockQuerier,
+) -> OwnedDeps<MockStorage, MockApi, CustomMockQuerier> {
     OwnedDeps {
         storage: MockStorage::default(),
         api: MockApi::default(),
-        querier: CustomMockQuerier::default(),
+        querier,
         custom_query_type: PhantomData,
     }
 }
+
+//------------------------------------------------------------------------------
+// Tests
+//------------------------------------------------------------------------------
+
+#[cfg(test)]
+pub(crate) mod test_helpers {
+    use cosmwasm_std::Addr;
+
+    use crate::AssetInfo;
+
+    /// Return the info of a mock CW20 token with the address `mock_token`,
+    /// whose balances can be set using e.g. `with_cw20_balance("mock_token", ..)`
+    pub fn mock_token() -> AssetInfo {
+        AssetInfo::cw20(Addr::unchecked("mock_token"))
+    }
+}
@@ -1,7 +1,14 @@
+//! Mock dependencies for unit-testing contracts that interact with assets
+//!
+//! Available with the `testing` feature.
+
 mod custom_mock_querier;
 mod cw20_querier;
+mod executor;
 mod helpers;
 
-#[allow(unused_imports)]
 pub use custom_mock_querier::CustomMockQuerier;
-pub use helpers::mock_dependencies;
+pub use cw20_querier::{Cw20Querier, Cw20State, Cw20TokenInfo};
+#[cfg(test)]
+pub(crate) use helpers::test_helpers::mock_token;
+pub use helpers::{mock_dependencies, mock_dependencies_with_querier};